@@ -0,0 +1,110 @@
+use std::fmt::{Display, Formatter};
+
+/// The raw value produced by a puzzle part
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum AnswerValue {
+    /// A numeric answer. Wide enough to hold any `u64` or `i64` result.
+    Integer(i128),
+    /// A single-line textual answer
+    Text(String),
+    /// A multi-line answer, such as an ASCII-art rendering
+    Lines(Vec<String>),
+}
+
+impl Display for AnswerValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Integer(val) => write!(f, "{val}"),
+            Self::Text(text) => write!(f, "{text}"),
+            Self::Lines(lines) => write!(f, "{}", lines.join("\n")),
+        }
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($($int:ty),*) => {
+        $(
+            impl From<$int> for AnswerValue {
+                fn from(value: $int) -> Self {
+                    Self::Integer(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl From<String> for AnswerValue {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<&str> for AnswerValue {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+impl From<Vec<String>> for AnswerValue {
+    fn from(value: Vec<String>) -> Self {
+        Self::Lines(value)
+    }
+}
+
+/// A labelled result returned from [AoCDay::part1](crate::AoCDay::part1) or
+/// [AoCDay::part2](crate::AoCDay::part2)
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Answer {
+    /// Short description of what the value represents, e.g. "Total distance"
+    pub label: String,
+    pub value: AnswerValue,
+}
+
+impl Answer {
+    pub fn new(label: impl Into<String>, value: impl Into<AnswerValue>) -> Self {
+        Self {
+            label: label.into(),
+            value: value.into(),
+        }
+    }
+
+    /// Returns the value as an integer, if it is one
+    pub fn as_integer(&self) -> Option<i128> {
+        match self.value {
+            AnswerValue::Integer(val) => Some(val),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.value {
+            AnswerValue::Lines(_) => write!(f, "{}:\n{}", self.label, self.value),
+            _ => write!(f, "{}: {}", self.label, self.value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integer_answer_display() {
+        let answer = Answer::new("Total distance", 11_u64);
+
+        assert_eq!(answer.as_integer(), Some(11));
+        assert_eq!(answer.to_string(), "Total distance: 11");
+    }
+
+    #[test]
+    fn lines_answer_display() {
+        let answer = Answer::new("Picture", vec!["#.".to_string(), ".#".to_string()]);
+
+        assert_eq!(answer.as_integer(), None);
+        assert_eq!(answer.to_string(), "Picture:\n#.\n.#");
+    }
+}
@@ -0,0 +1,108 @@
+use std::fmt::{Display, Formatter};
+
+/// A problem found at a specific place in the puzzle input
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Diagnostic {
+    /// One-based line number
+    pub line: usize,
+    /// One-based column, counted in characters
+    pub column: usize,
+    /// The full line of input containing the problem, without its line ending
+    pub source_line: String,
+    /// Length of the offending text in characters; zero at the end of a line or the input
+    pub len: usize,
+    /// The offending text, or a description such as "end of line"
+    pub found: String,
+    /// Description of what should have been there
+    pub expected: String,
+}
+
+impl Diagnostic {
+    /// Creates a diagnostic for the text starting `offset` bytes into `input`
+    ///
+    /// The offending text is taken to be the word or single symbol at `offset`.
+    pub fn at(input: &str, offset: usize, expected: impl Into<String>) -> Self {
+        let consumed = &input[..offset];
+        let line_start = consumed.rfind('\n').map_or(0, |idx| idx + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |idx| offset + idx);
+        let source_line = input[line_start..line_end].trim_end_matches('\r');
+
+        let rest = &input[offset..line_end.max(offset)];
+        let rest = rest.trim_end_matches('\r');
+        let token: String = match rest.chars().next() {
+            Some(c) if c.is_alphanumeric() => {
+                rest.chars().take_while(|c| c.is_alphanumeric()).collect()
+            }
+            Some(c) => c.to_string(),
+            None => String::new(),
+        };
+        let (len, found) = if !token.is_empty() {
+            (
+                token.chars().count(),
+                format!("'{}'", token.escape_default()),
+            )
+        } else if input[offset..].trim().is_empty() {
+            (0, "end of input".to_string())
+        } else {
+            (0, "end of line".to_string())
+        };
+
+        Self {
+            line: consumed.matches('\n').count() + 1,
+            column: consumed[line_start..].chars().count() + 1,
+            source_line: source_line.to_string(),
+            len,
+            found,
+            expected: expected.into(),
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found {}",
+            self.line, self.column, self.expected, self.found
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "3   4\n4   x3\n2   5\n";
+
+    #[test]
+    fn locates_offending_token() {
+        let diagnostic = Diagnostic::at(INPUT, 10, "a number");
+
+        assert_eq!((diagnostic.line, diagnostic.column), (2, 5));
+        assert_eq!(diagnostic.source_line, "4   x3");
+        assert_eq!((diagnostic.len, diagnostic.found.as_str()), (2, "'x3'"));
+        assert_eq!(
+            diagnostic.to_string(),
+            "line 2, column 5: expected a number, found 'x3'"
+        );
+    }
+
+    #[test]
+    fn describes_line_and_input_ends() {
+        let end_of_line = Diagnostic::at(INPUT, 5, "a number");
+        assert_eq!((end_of_line.line, end_of_line.column), (1, 6));
+        assert_eq!(
+            (end_of_line.len, end_of_line.found.as_str()),
+            (0, "end of line")
+        );
+
+        let end_of_input = Diagnostic::at(INPUT, INPUT.len(), "a number");
+        assert_eq!(end_of_input.line, 4);
+        assert_eq!(end_of_input.found, "end of input");
+
+        let symbol = Diagnostic::at("1,,2", 2, "a number");
+        assert_eq!((symbol.len, symbol.found.as_str()), (1, "','"));
+    }
+}
@@ -0,0 +1,82 @@
+use crate::Diagnostic;
+use std::fmt::{Display, Formatter};
+
+/// Errors which may occur while loading input for, or running, a puzzle part
+#[derive(Debug)]
+pub enum AoCError {
+    /// The requested part has not been solved yet
+    NotImplemented,
+    /// The puzzle input could not be understood
+    BadInput(String),
+    /// The puzzle input could not be parsed, at a known location
+    Parse(Diagnostic),
+    /// Anything else that went wrong, such as I/O failures
+    Internal(anyhow::Error),
+}
+
+/// Shorthand for results produced by [AoCDay](crate::AoCDay) implementations
+pub type AoCResult<T> = Result<T, AoCError>;
+
+impl AoCError {
+    pub fn bad_input(reason: impl Display) -> Self {
+        Self::BadInput(reason.to_string())
+    }
+}
+
+impl Display for AoCError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotImplemented => write!(f, "Not implemented yet"),
+            Self::BadInput(reason) => write!(f, "Bad input: {reason}"),
+            Self::Parse(diagnostic) => write!(f, "Bad input: {diagnostic}"),
+            Self::Internal(err) => write!(f, "Internal error: {err:#}"),
+        }
+    }
+}
+
+impl std::error::Error for AoCError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Internal(err) => Some(err.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<anyhow::Error> for AoCError {
+    fn from(err: anyhow::Error) -> Self {
+        Self::Internal(err)
+    }
+}
+
+impl From<Diagnostic> for AoCError {
+    fn from(diagnostic: Diagnostic) -> Self {
+        Self::Parse(diagnostic)
+    }
+}
+
+impl From<std::io::Error> for AoCError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Internal(err.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn io_error_is_internal() {
+        let err: AoCError = std::io::Error::other("disk on fire").into();
+
+        assert!(matches!(err, AoCError::Internal(_)));
+        assert_eq!(err.to_string(), "Internal error: disk on fire");
+    }
+
+    #[test]
+    fn bad_input_display() {
+        let err = AoCError::bad_input("missing guard");
+
+        assert_eq!(err.to_string(), "Bad input: missing guard");
+    }
+}
@@ -0,0 +1,36 @@
+/// An example input from a puzzle description, along with the answers it is known to produce
+///
+/// Examples are declared when registering a day so that the runner can check them without
+/// access to the day's source directory.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Example {
+    /// Name used to select the example, e.g. `example`
+    pub name: &'static str,
+    /// Full text of the example input
+    pub input: &'static str,
+    /// Expected answer to part 1, as displayed, if the example applies to part 1
+    pub part1: Option<&'static str>,
+    /// Expected answer to part 2, as displayed, if the example applies to part 2
+    pub part2: Option<&'static str>,
+}
+
+impl Example {
+    pub const fn new(name: &'static str, input: &'static str) -> Self {
+        Self {
+            name,
+            input,
+            part1: None,
+            part2: None,
+        }
+    }
+
+    pub const fn part1(mut self, expected: &'static str) -> Self {
+        self.part1 = Some(expected);
+        self
+    }
+
+    pub const fn part2(mut self, expected: &'static str) -> Self {
+        self.part2 = Some(expected);
+        self
+    }
+}
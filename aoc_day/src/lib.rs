@@ -1,16 +1,116 @@
+use std::io::Read;
 use std::path::Path;
 
+mod answer;
+mod diagnostic;
+mod error;
+mod example;
+#[doc(hidden)]
+pub mod registry;
+mod solution;
+
+pub use answer::{Answer, AnswerValue};
+pub use diagnostic::Diagnostic;
+pub use error::{AoCError, AoCResult};
+pub use example::Example;
+pub use registry::{find_day, registered_days, DayRegistration};
+pub use solution::{Solution, SolutionDay};
+
+#[doc(hidden)]
+pub use inventory;
+
 pub trait AoCDay {
     /// Part 1 implementation
     ///
-    /// Result must be printed to the console.
-    fn part1(&mut self);
+    /// Returns the labelled [Answer] rather than printing it, so the caller decides how it is
+    /// displayed or stored.
+    fn part1(&mut self) -> AoCResult<Answer>;
 
     /// Part 2 implementation
     ///
-    /// Result must be printed to the console.
-    fn part2(&mut self);
+    /// Returns the labelled [Answer] rather than printing it, so the caller decides how it is
+    /// displayed or stored.
+    fn part2(&mut self) -> AoCResult<Answer>;
+
+    /// Load the Day's input from the full text of the puzzle input
+    ///
+    /// Malformed input should be reported as [AoCError::Parse] when the offending location is
+    /// known, or [AoCError::BadInput] otherwise.
+    fn load_str(&mut self, input: &str) -> AoCResult<()>;
+
+    /// Load the Day's input from any reader, such as stdin
+    fn load_reader(&mut self, reader: &mut dyn Read) -> AoCResult<()> {
+        let mut input = String::new();
+        reader.read_to_string(&mut input)?;
+        self.load_str(&input)
+    }
 
     /// Load the Day's input from the given path
-    fn load_input(&mut self, path: &Path) -> anyhow::Result<()>;
+    fn load_input(&mut self, path: &Path) -> AoCResult<()> {
+        let input = std::fs::read_to_string(path)?;
+        self.load_str(&input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct LineCount {
+        lines: usize,
+    }
+
+    impl AoCDay for LineCount {
+        fn part1(&mut self) -> AoCResult<Answer> {
+            Ok(Answer::new("Lines", self.lines))
+        }
+
+        fn part2(&mut self) -> AoCResult<Answer> {
+            Err(AoCError::NotImplemented)
+        }
+
+        fn load_str(&mut self, input: &str) -> AoCResult<()> {
+            self.lines = input.lines().count();
+            Ok(())
+        }
+    }
+
+    crate::register_day!(25, "Line Count", LineCount, examples: [
+        Example::new("three", "a\nb\nc\n").part1("3"),
+    ]);
+
+    #[test]
+    fn registered_day_is_found() {
+        let registration = find_day(25).unwrap();
+        assert_eq!(registration.title, "Line Count");
+
+        let mut day = registration.create();
+        day.load_str("a\nb\n").unwrap();
+        assert_eq!(day.part1().unwrap().as_integer(), Some(2));
+
+        let example = registration.example("three").unwrap();
+        assert_eq!(example.part1, Some("3"));
+        assert_eq!(example.part2, None);
+        assert!(registration.example("four").is_none());
+
+        assert!(find_day(24).is_none());
+        assert_eq!(registered_days().len(), 1);
+    }
+
+    #[test]
+    fn load_reader_uses_load_str() {
+        let mut day = LineCount::default();
+        day.load_reader(&mut "a\nb\nc\n".as_bytes()).unwrap();
+
+        assert_eq!(day.part1().unwrap().as_integer(), Some(3));
+    }
+
+    #[test]
+    fn load_input_missing_file_is_internal() {
+        let mut day = LineCount::default();
+        let result = day.load_input(Path::new("does/not/exist.txt"));
+
+        assert!(matches!(result, Err(AoCError::Internal(_))));
+    }
 }
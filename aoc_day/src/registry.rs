@@ -0,0 +1,87 @@
+use crate::{AoCDay, Example};
+
+/// Describes a day crate that can be run by `run-aoc`
+///
+/// Entries are not created by hand. Use [register_day](crate::register_day) in the day's crate
+/// and the runner will discover it through [registered_days].
+pub struct DayRegistration {
+    /// Day of the calendar, 1 through 25
+    pub day: u8,
+    /// Puzzle title as shown on the calendar
+    pub title: &'static str,
+    /// Examples from the puzzle description
+    pub examples: &'static [Example],
+    constructor: fn() -> Box<dyn AoCDay>,
+}
+
+impl DayRegistration {
+    #[doc(hidden)]
+    pub const fn new(
+        day: u8,
+        title: &'static str,
+        examples: &'static [Example],
+        constructor: fn() -> Box<dyn AoCDay>,
+    ) -> Self {
+        Self {
+            day,
+            title,
+            examples,
+            constructor,
+        }
+    }
+
+    /// Looks up one of the day's examples by name
+    pub fn example(&self, name: &str) -> Option<&'static Example> {
+        self.examples.iter().find(|example| example.name == name)
+    }
+
+    /// Creates a fresh instance of the day, ready for input to be loaded
+    pub fn create(&self) -> Box<dyn AoCDay> {
+        (self.constructor)()
+    }
+}
+
+inventory::collect!(DayRegistration);
+
+#[doc(hidden)]
+pub fn construct<T: AoCDay + Default + 'static>() -> Box<dyn AoCDay> {
+    Box::new(T::default())
+}
+
+/// Registers an [AoCDay] implementation so that the runner can find it
+///
+/// The type must implement [Default]. Any [Example]s from the puzzle description may be listed
+/// after the type.
+///
+/// ```text
+/// aoc_day::register_day!(1, "Historian Hysteria", Day01, examples: [
+///     Example::new("example", include_str!("../example_input.txt")).part1("11"),
+/// ]);
+/// ```
+#[macro_export]
+macro_rules! register_day {
+    ($day:expr, $title:expr, $ty:ty $(, examples: [$($example:expr),* $(,)?])? $(,)?) => {
+        $crate::inventory::submit! {
+            $crate::DayRegistration::new(
+                $day,
+                $title,
+                &[$($($example),*)?],
+                $crate::registry::construct::<$ty>,
+            )
+        }
+    };
+}
+
+/// All registered days, ordered by day number
+pub fn registered_days() -> Vec<&'static DayRegistration> {
+    let mut days: Vec<_> = inventory::iter::<DayRegistration>.into_iter().collect();
+    days.sort_unstable_by_key(|registration| registration.day);
+    days
+}
+
+/// Looks up the registration for the given day, if one exists
+pub fn find_day(day: u8) -> Option<&'static DayRegistration> {
+    inventory::iter::<DayRegistration>
+        .into_iter()
+        .find(|registration| registration.day == day)
+}
@@ -0,0 +1,100 @@
+use crate::{Answer, AoCDay, AoCError, AoCResult};
+use anyhow::anyhow;
+
+/// A day whose parts are pure functions of the parsed input
+///
+/// Unlike [AoCDay], nothing is stored between calls, so parts may be run repeatedly or in
+/// parallel against the same parsed input. Wrap the type in [SolutionDay] to register it with
+/// the runner.
+pub trait Solution {
+    /// Parsed form of the puzzle input
+    type Input;
+
+    /// Parses the full text of the puzzle input
+    ///
+    /// Malformed input should be reported as [AoCError::Parse] when the offending location is
+    /// known, or [AoCError::BadInput] otherwise.
+    fn parse(input: &str) -> AoCResult<Self::Input>;
+
+    /// Part 1 implementation
+    fn part1(input: &Self::Input) -> AoCResult<Answer>;
+
+    /// Part 2 implementation
+    fn part2(_input: &Self::Input) -> AoCResult<Answer> {
+        Err(AoCError::NotImplemented)
+    }
+}
+
+/// Adapts a [Solution] to the stateful [AoCDay] interface used by the runner
+pub struct SolutionDay<S: Solution> {
+    input: Option<S::Input>,
+}
+
+impl<S: Solution> Default for SolutionDay<S> {
+    fn default() -> Self {
+        Self { input: None }
+    }
+}
+
+impl<S: Solution> SolutionDay<S> {
+    fn loaded_input(&self) -> AoCResult<&S::Input> {
+        self.input.as_ref().ok_or_else(|| {
+            AoCError::Internal(anyhow!("Input must be loaded before running a part"))
+        })
+    }
+}
+
+impl<S: Solution> AoCDay for SolutionDay<S> {
+    fn part1(&mut self) -> AoCResult<Answer> {
+        S::part1(self.loaded_input()?)
+    }
+
+    fn part2(&mut self) -> AoCResult<Answer> {
+        S::part2(self.loaded_input()?)
+    }
+
+    fn load_str(&mut self, input: &str) -> AoCResult<()> {
+        self.input = Some(S::parse(input)?);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct WordCount;
+
+    impl Solution for WordCount {
+        type Input = Vec<String>;
+
+        fn parse(input: &str) -> AoCResult<Self::Input> {
+            if input.is_empty() {
+                return Err(AoCError::bad_input("empty input"));
+            }
+            Ok(input.split_whitespace().map(str::to_string).collect())
+        }
+
+        fn part1(input: &Self::Input) -> AoCResult<Answer> {
+            Ok(Answer::new("Words", input.len()))
+        }
+    }
+
+    #[test]
+    fn adapter_runs_parts_repeatedly() {
+        let mut day = SolutionDay::<WordCount>::default();
+        day.load_str("one two three").unwrap();
+
+        assert_eq!(day.part1().unwrap().as_integer(), Some(3));
+        assert_eq!(day.part1().unwrap().as_integer(), Some(3));
+        assert!(matches!(day.part2(), Err(AoCError::NotImplemented)));
+    }
+
+    #[test]
+    fn adapter_requires_input() {
+        let mut day = SolutionDay::<WordCount>::default();
+
+        assert!(matches!(day.part1(), Err(AoCError::Internal(_))));
+        assert!(matches!(day.load_str(""), Err(AoCError::BadInput(_))));
+    }
+}
@@ -0,0 +1,46 @@
+use std::fmt::{Display, Formatter};
+
+/// Reasons text could not be parsed into a [Grid](crate::Grid)
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum GridError {
+    /// The text contained no rows
+    Empty,
+    /// A row's length differed from the first row's
+    Ragged {
+        /// Zero-based row index
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// A character could not be converted into a cell
+    InvalidCell { x: usize, y: usize, found: char },
+    /// The number of cells does not fill the requested dimensions
+    SizeMismatch {
+        width: usize,
+        height: usize,
+        cells: usize,
+    },
+}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "grid has no rows"),
+            Self::Ragged {
+                row,
+                expected,
+                found,
+            } => write!(f, "row {} has {found} cells, expected {expected}", row + 1),
+            Self::InvalidCell { x, y, found } => {
+                write!(f, "unexpected '{found}' at row {}, column {}", y + 1, x + 1)
+            }
+            Self::SizeMismatch {
+                width,
+                height,
+                cells,
+            } => write!(f, "{cells} cells cannot fill a {width}x{height} grid"),
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
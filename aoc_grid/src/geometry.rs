@@ -0,0 +1,278 @@
+use crate::Position;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A signed coordinate, with `y` growing downwards to match grid rows
+///
+/// Unlike [Position], a point may lie outside any grid, so stepping off an edge cannot underflow.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Point {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point::new(0, 0);
+
+    pub const fn new(x: isize, y: isize) -> Self {
+        Self { x, y }
+    }
+
+    /// Converts to grid indices, if neither coordinate is negative
+    pub fn to_position(self) -> Option<Position> {
+        Some((usize::try_from(self.x).ok()?, usize::try_from(self.y).ok()?))
+    }
+
+    /// Taxicab distance between two points
+    pub fn manhattan_distance(self, other: Point) -> usize {
+        (other - self).manhattan_length()
+    }
+}
+
+impl From<Position> for Point {
+    fn from((x, y): Position) -> Self {
+        Self::new(x as isize, y as isize)
+    }
+}
+
+/// A signed displacement between two [Point]s
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Vector {
+    pub dx: isize,
+    pub dy: isize,
+}
+
+impl Vector {
+    pub const ZERO: Vector = Vector::new(0, 0);
+
+    pub const fn new(dx: isize, dy: isize) -> Self {
+        Self { dx, dy }
+    }
+
+    pub fn manhattan_length(self) -> usize {
+        self.dx.unsigned_abs() + self.dy.unsigned_abs()
+    }
+}
+
+impl Add<Vector> for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Vector) -> Point {
+        Point::new(self.x + rhs.dx, self.y + rhs.dy)
+    }
+}
+
+impl AddAssign<Vector> for Point {
+    fn add_assign(&mut self, rhs: Vector) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<Vector> for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Vector) -> Point {
+        Point::new(self.x - rhs.dx, self.y - rhs.dy)
+    }
+}
+
+impl SubAssign<Vector> for Point {
+    fn sub_assign(&mut self, rhs: Vector) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sub<Point> for Point {
+    type Output = Vector;
+
+    fn sub(self, rhs: Point) -> Vector {
+        Vector::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, rhs: Vector) -> Vector {
+        Vector::new(self.dx + rhs.dx, self.dy + rhs.dy)
+    }
+}
+
+impl Sub for Vector {
+    type Output = Vector;
+
+    fn sub(self, rhs: Vector) -> Vector {
+        Vector::new(self.dx - rhs.dx, self.dy - rhs.dy)
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Vector {
+        Vector::new(-self.dx, -self.dy)
+    }
+}
+
+impl Mul<isize> for Vector {
+    type Output = Vector;
+
+    fn mul(self, rhs: isize) -> Vector {
+        Vector::new(self.dx * rhs, self.dy * rhs)
+    }
+}
+
+/// One of the four directions which do not move diagonally
+#[derive(Default, Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub enum Direction4 {
+    #[default]
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction4 {
+    /// Every direction, clockwise from [Direction4::North]
+    pub const ALL: [Direction4; 4] = [Self::North, Self::East, Self::South, Self::West];
+
+    /// The direction 90° clockwise from this one
+    pub fn turn_right(self) -> Self {
+        match self {
+            Self::North => Self::East,
+            Self::East => Self::South,
+            Self::South => Self::West,
+            Self::West => Self::North,
+        }
+    }
+
+    /// The direction 90° anticlockwise from this one
+    pub fn turn_left(self) -> Self {
+        self.turn_right().reverse()
+    }
+
+    pub fn reverse(self) -> Self {
+        self.turn_right().turn_right()
+    }
+
+    /// A single step in this direction
+    pub fn delta(self) -> Vector {
+        Direction8::from(self).delta()
+    }
+}
+
+/// One of the eight compass directions
+#[derive(Default, Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub enum Direction8 {
+    #[default]
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction8 {
+    /// Every direction, clockwise from [Direction8::North]
+    pub const ALL: [Direction8; 8] = [
+        Self::North,
+        Self::NorthEast,
+        Self::East,
+        Self::SouthEast,
+        Self::South,
+        Self::SouthWest,
+        Self::West,
+        Self::NorthWest,
+    ];
+
+    /// The direction 45° clockwise from this one
+    pub fn rotate_clockwise(self) -> Self {
+        Self::ALL[(self as usize + 1) % Self::ALL.len()]
+    }
+
+    /// The direction 45° anticlockwise from this one
+    pub fn rotate_anticlockwise(self) -> Self {
+        Self::ALL[(self as usize + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    /// The direction 90° clockwise from this one
+    pub fn turn_right(self) -> Self {
+        self.rotate_clockwise().rotate_clockwise()
+    }
+
+    /// The direction 90° anticlockwise from this one
+    pub fn turn_left(self) -> Self {
+        self.rotate_anticlockwise().rotate_anticlockwise()
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 4) % Self::ALL.len()]
+    }
+
+    /// A single step in this direction
+    pub fn delta(self) -> Vector {
+        match self {
+            Self::North => Vector::new(0, -1),
+            Self::NorthEast => Vector::new(1, -1),
+            Self::East => Vector::new(1, 0),
+            Self::SouthEast => Vector::new(1, 1),
+            Self::South => Vector::new(0, 1),
+            Self::SouthWest => Vector::new(-1, 1),
+            Self::West => Vector::new(-1, 0),
+            Self::NorthWest => Vector::new(-1, -1),
+        }
+    }
+}
+
+impl From<Direction4> for Direction8 {
+    fn from(direction: Direction4) -> Self {
+        match direction {
+            Direction4::North => Self::North,
+            Direction4::East => Self::East,
+            Direction4::South => Self::South,
+            Direction4::West => Self::West,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn point_arithmetic() {
+        let start = Point::new(2, 3);
+        let end = start + Direction4::North.delta() * 4;
+
+        assert_eq!(end, Point::new(2, -1));
+        assert_eq!(end - start, Vector::new(0, -4));
+        assert_eq!(end.to_position(), None);
+        assert_eq!(start.to_position(), Some((2, 3)));
+        assert_eq!(Point::from((5, 1)).manhattan_distance(Point::new(2, 3)), 5);
+    }
+
+    #[test]
+    fn direction4_rotation() {
+        assert_eq!(Direction4::West.turn_right(), Direction4::North);
+        assert_eq!(Direction4::North.turn_left(), Direction4::West);
+        assert_eq!(Direction4::East.reverse(), Direction4::West);
+        assert_eq!(Direction4::South.delta(), Vector::new(0, 1));
+    }
+
+    #[test]
+    fn direction8_rotation() {
+        assert_eq!(Direction8::NorthWest.rotate_clockwise(), Direction8::North);
+        assert_eq!(
+            Direction8::North.rotate_anticlockwise(),
+            Direction8::NorthWest
+        );
+        assert_eq!(Direction8::NorthEast.turn_right(), Direction8::SouthEast);
+        assert_eq!(Direction8::SouthWest.reverse(), Direction8::NorthEast);
+        assert_eq!(
+            -Direction8::SouthWest.delta(),
+            Direction8::NorthEast.delta()
+        );
+    }
+}
@@ -0,0 +1,391 @@
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+mod error;
+mod geometry;
+
+pub use error::GridError;
+pub use geometry::{Direction4, Direction8, Point, Vector};
+
+/// An `(x, y)` cell position, with `(0, 0)` in the top-left corner
+pub type Position = (usize, usize);
+
+/// A rectangular grid of cells stored row by row
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from `cells` given in row order
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Result<Self, GridError> {
+        if cells.len() != width * height {
+            return Err(GridError::SizeMismatch {
+                width,
+                height,
+                cells: cells.len(),
+            });
+        }
+
+        Ok(Self {
+            cells,
+            width,
+            height,
+        })
+    }
+
+    /// Creates a grid with every cell set to `value`
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: vec![value; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Parses one row per line of `text`, converting each character with `parse_cell`
+    ///
+    /// Trailing blank lines are ignored; every other row must be as wide as the first.
+    pub fn parse_with(
+        text: &str,
+        mut parse_cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, GridError> {
+        let mut lines: Vec<&str> = text.lines().collect();
+        while lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
+        }
+
+        let Some(first) = lines.first() else {
+            return Err(GridError::Empty);
+        };
+        let width = first.chars().count();
+        let height = lines.len();
+
+        let mut cells = Vec::with_capacity(width * height);
+        for (y, line) in lines.iter().enumerate() {
+            let row_start = cells.len();
+            for (x, c) in line.chars().enumerate() {
+                let cell = parse_cell(c).ok_or(GridError::InvalidCell { x, y, found: c })?;
+                cells.push(cell);
+            }
+
+            let found = cells.len() - row_start;
+            if found != width {
+                return Err(GridError::Ragged {
+                    row: y,
+                    expected: width,
+                    found,
+                });
+            }
+        }
+
+        Ok(Self {
+            cells,
+            width,
+            height,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether `point` lies within the grid
+    pub fn contains(&self, point: Point) -> bool {
+        self.to_position(point).is_some()
+    }
+
+    /// Converts `point` to the position of a cell, if it lies within the grid
+    pub fn to_position(&self, point: Point) -> Option<Position> {
+        point
+            .to_position()
+            .filter(|&(x, y)| x < self.width && y < self.height)
+    }
+
+    pub fn get(&self, (x, y): Position) -> Option<&T> {
+        if x < self.width && y < self.height {
+            self.cells.get(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, (x, y): Position) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            self.cells.get_mut(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    /// Looks up a cell by signed coordinate, returning `None` for anything outside the grid
+    pub fn get_point(&self, point: Point) -> Option<&T> {
+        self.get(self.to_position(point)?)
+    }
+
+    /// The position `delta` away from `pos`, if it is within the grid
+    pub fn offset(&self, pos: Position, delta: Vector) -> Option<Position> {
+        self.to_position(Point::from(pos) + delta)
+    }
+
+    /// The cells directly above, right of, below and left of `pos`, where they exist
+    pub fn neighbours4(&self, pos: Position) -> impl Iterator<Item = (Position, &T)> {
+        self.neighbours_in(pos, Direction4::ALL.map(Direction4::delta))
+    }
+
+    /// The up to eight cells surrounding `pos`, including diagonals
+    pub fn neighbours8(&self, pos: Position) -> impl Iterator<Item = (Position, &T)> {
+        self.neighbours_in(pos, Direction8::ALL.map(Direction8::delta))
+    }
+
+    fn neighbours_in<const N: usize>(
+        &self,
+        pos: Position,
+        deltas: [Vector; N],
+    ) -> impl Iterator<Item = (Position, &T)> {
+        deltas.into_iter().filter_map(move |delta| {
+            let neighbour = self.offset(pos, delta)?;
+            Some((neighbour, &self[neighbour]))
+        })
+    }
+
+    /// Walks from `start` in `direction` until leaving the grid, starting with `start` itself
+    pub fn ray(
+        &self,
+        start: Position,
+        direction: impl Into<Direction8>,
+    ) -> impl Iterator<Item = (Position, &T)> {
+        let delta = direction.into().delta();
+        let first = self.get(start).map(|_| start);
+
+        std::iter::successors(first, move |&pos| self.offset(pos, delta))
+            .map(|pos| (pos, &self[pos]))
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height).then(|| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.ray((x, 0), Direction8::South).map(|(_, cell)| cell)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Every diagonal running from top-left to bottom-right, starting at the top-right corner
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let top = (0..self.width).rev().map(|x| (x, 0));
+        let left = (1..self.height).map(|y| (0, y));
+
+        top.chain(left)
+            .map(|start| self.ray(start, Direction8::SouthEast).map(|(_, cell)| cell))
+    }
+
+    /// Every diagonal running from top-right to bottom-left, starting at the top-left corner
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let top = (0..self.width).map(|x| (x, 0));
+        let right = (1..self.height).map(|y| (self.width.saturating_sub(1), y));
+
+        top.chain(right)
+            .map(|start| self.ray(start, Direction8::SouthWest).map(|(_, cell)| cell))
+    }
+
+    /// Every position in row order
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Every cell along with its position, in row order
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// The first position, in row order, holding `value`
+    pub fn find(&self, value: &T) -> Option<Position>
+    where
+        T: PartialEq,
+    {
+        self.find_all(value).next()
+    }
+
+    /// Every position holding `value`, in row order
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Position> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, cell)| *cell == value)
+            .map(|(pos, _)| pos)
+    }
+
+    /// Converts every cell, keeping the grid's shape
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Position) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside a {}x{} grid", self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, pos: Position) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside a {width}x{height} grid"))
+    }
+}
+
+impl<T: TryFrom<char>> FromStr for Grid<T> {
+    type Err = GridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, |c| T::try_from(c).ok())
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "abc\ndef\nghi\n";
+
+    fn collect<'a>(cells: impl Iterator<Item = &'a char>) -> String {
+        cells.collect()
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let grid: Grid<char> = TEXT.parse().unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid[(2, 1)], 'f');
+        assert_eq!(grid.to_string(), TEXT.trim_end());
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!("".parse::<Grid<char>>(), Err(GridError::Empty));
+        assert_eq!(
+            "abc\nde\n".parse::<Grid<char>>(),
+            Err(GridError::Ragged {
+                row: 1,
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(
+            Grid::parse_with("#.\n.x", |c| match c {
+                '#' => Some(true),
+                '.' => Some(false),
+                _ => None,
+            }),
+            Err(GridError::InvalidCell {
+                x: 1,
+                y: 1,
+                found: 'x'
+            })
+        );
+    }
+
+    #[test]
+    fn signed_access() {
+        let grid: Grid<char> = TEXT.parse().unwrap();
+        assert_eq!(grid.get_point(Point::new(0, 0)), Some(&'a'));
+        assert_eq!(grid.get_point(Point::new(-1, 0)), None);
+        assert_eq!(grid.get_point(Point::new(0, 3)), None);
+        assert!(!grid.contains(Point::new(3, 0)));
+        assert_eq!(grid.offset((0, 0), Direction4::West.delta()), None);
+        assert_eq!(grid.offset((0, 0), Vector::new(2, 1)), Some((2, 1)));
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid: Grid<char> = TEXT.parse().unwrap();
+
+        let corner: String = grid.neighbours4((0, 0)).map(|(_, c)| *c).collect();
+        assert_eq!(corner, "bd");
+
+        let centre: String = grid.neighbours8((1, 1)).map(|(_, c)| *c).collect();
+        assert_eq!(centre, "bcfihgda");
+    }
+
+    #[test]
+    fn rays_and_views() {
+        let grid: Grid<char> = TEXT.parse().unwrap();
+
+        let ray: String = grid
+            .ray((0, 2), Direction8::NorthEast)
+            .map(|(_, c)| *c)
+            .collect();
+        assert_eq!(ray, "gec");
+        assert_eq!(grid.ray((3, 0), Direction4::South).count(), 0);
+
+        assert_eq!(grid.row(1), Some(&['d', 'e', 'f'][..]));
+        assert_eq!(grid.row(3), None);
+        assert_eq!(collect(grid.column(2)), "cfi");
+        assert_eq!(
+            grid.columns().map(collect).collect::<Vec<_>>(),
+            ["adg", "beh", "cfi"]
+        );
+        assert_eq!(
+            grid.diagonals().map(collect).collect::<Vec<_>>(),
+            ["c", "bf", "aei", "dh", "g"]
+        );
+        assert_eq!(
+            grid.anti_diagonals().map(collect).collect::<Vec<_>>(),
+            ["a", "bd", "ceg", "fh", "i"]
+        );
+    }
+
+    #[test]
+    fn find_and_map() {
+        let grid: Grid<char> = "#..\n.#.\n".parse().unwrap();
+        assert_eq!(grid.find(&'#'), Some((0, 0)));
+        assert_eq!(grid.find_all(&'#').collect::<Vec<_>>(), [(0, 0), (1, 1)]);
+        assert_eq!(grid.find(&'x'), None);
+
+        let walls = grid.map(|c| u8::from(*c == '#'));
+        assert_eq!(walls.to_string(), "100\n010");
+    }
+}
@@ -0,0 +1,33 @@
+use aoc_day::Diagnostic;
+use nom::error::{VerboseError, VerboseErrorKind};
+use nom::Offset;
+
+/// Creates a diagnostic located at the start of `remaining`, which must be a slice of `input`
+pub fn diagnostic_at(input: &str, remaining: &str, expected: impl Into<String>) -> Diagnostic {
+    Diagnostic::at(input, input.offset(remaining), expected)
+}
+
+/// Converts an error produced while parsing `input`
+///
+/// The error is reported at the innermost failure, described by the innermost `context` attached
+/// to it.
+pub fn from_verbose(input: &str, err: VerboseError<&str>) -> Diagnostic {
+    let Some(&(remaining, ref kind)) = err.errors.first() else {
+        return Diagnostic::at(input, 0, "valid input");
+    };
+
+    let expected = err
+        .errors
+        .iter()
+        .find_map(|(_, kind)| match kind {
+            VerboseErrorKind::Context(context) => Some(context.to_string()),
+            _ => None,
+        })
+        .unwrap_or_else(|| match kind {
+            VerboseErrorKind::Char(c) => format!("'{}'", c.escape_default()),
+            VerboseErrorKind::Nom(kind) => kind.description().to_lowercase(),
+            VerboseErrorKind::Context(context) => context.to_string(),
+        });
+
+    diagnostic_at(input, remaining, expected)
+}
@@ -0,0 +1,255 @@
+use aoc_day::Diagnostic;
+use aoc_grid::Grid;
+use nom::branch::alt;
+use nom::bytes::complete::take_till1;
+use nom::character::complete::{char, digit1, line_ending, one_of, space0, space1};
+use nom::combinator::{cut, eof, map_res, not, opt, recognize};
+use nom::error::{context, VerboseError, VerboseErrorKind};
+use nom::multi::separated_list1;
+use nom::sequence::{pair, separated_pair};
+use nom::{IResult, Parser};
+use std::str::FromStr;
+
+mod error;
+
+pub use error::diagnostic_at;
+
+/// Result of the parsers in this crate, which keep enough detail to build a [Diagnostic]
+pub type PResult<'a, O> = IResult<&'a str, O, VerboseError<&'a str>>;
+
+/// Runs `parser` over the whole of `input`
+///
+/// Trailing whitespace is allowed; anything else left over is reported as an error.
+pub fn parse_all<'a, O>(
+    mut parser: impl Parser<&'a str, O, VerboseError<&'a str>>,
+    input: &'a str,
+) -> Result<O, Diagnostic> {
+    match parser.parse(input) {
+        Ok((rest, output)) if rest.trim().is_empty() => Ok(output),
+        Ok((rest, _)) => Err(diagnostic_at(input, rest, "end of input")),
+        Err(nom::Err::Error(err) | nom::Err::Failure(err)) => Err(error::from_verbose(input, err)),
+        Err(nom::Err::Incomplete(_)) => Err(Diagnostic::at(input, input.len(), "more input")),
+    }
+}
+
+/// An unrecoverable error at `input`, expecting `expected`
+fn failure<'a>(input: &'a str, expected: &'static str) -> nom::Err<VerboseError<&'a str>> {
+    nom::Err::Failure(VerboseError {
+        errors: vec![(input, VerboseErrorKind::Context(expected))],
+    })
+}
+
+/// A non-negative decimal number
+pub fn unsigned<T: FromStr>(input: &str) -> PResult<'_, T> {
+    context("an unsigned number", map_res(digit1, str::parse))(input)
+}
+
+/// A decimal number with an optional sign
+pub fn signed<T: FromStr>(input: &str) -> PResult<'_, T> {
+    context(
+        "a number",
+        map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse),
+    )(input)
+}
+
+/// One or more `item`s separated by `separator`, such as `75,47,61`
+///
+/// Every separator must be followed by another item.
+pub fn list<'a, O>(
+    separator: char,
+    item: impl Parser<&'a str, O, VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> PResult<'a, Vec<O>> {
+    separated_list1(char(separator), cut(item))
+}
+
+/// One or more `item`s on a single line, separated by spaces or tabs
+///
+/// Spacing which is not at the end of the line must be followed by another item.
+pub fn spaced<'a, O>(
+    item: impl Parser<&'a str, O, VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> PResult<'a, Vec<O>> {
+    let separator = pair(space1, not(alt((line_ending, eof))));
+    separated_list1(separator, cut(item))
+}
+
+/// `key: value`, with any amount of spacing after the colon
+pub fn key_value<'a, K, V>(
+    key: impl Parser<&'a str, K, VerboseError<&'a str>>,
+    value: impl Parser<&'a str, V, VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> PResult<'a, (K, V)> {
+    separated_pair(key, pair(char(':'), space0), value)
+}
+
+/// One `record` per line, stopping at a blank line or the end of input
+///
+/// Each record must fill its line. The line ending after the final record is not consumed, so a
+/// following blank line can still separate sections.
+pub fn lines_of<'a, O>(
+    mut record: impl Parser<&'a str, O, VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> PResult<'a, Vec<O>> {
+    move |input: &'a str| {
+        let mut records = Vec::new();
+        let mut rest = input;
+
+        loop {
+            let (after, item) = cut(|i| record.parse(i))(rest)?;
+            records.push(item);
+            rest = after;
+
+            if rest.is_empty() {
+                return Ok((rest, records));
+            }
+            let (next_line, _) = line_ending::<_, VerboseError<_>>(rest)
+                .map_err(|_| failure(rest, "end of line"))?;
+            if next_line.is_empty() || line_ending::<_, VerboseError<_>>(next_line).is_ok() {
+                return Ok((rest, records));
+            }
+            rest = next_line;
+        }
+    }
+}
+
+/// A blank line separating two sections
+fn blank_line(input: &str) -> PResult<'_, &str> {
+    context("a blank line", recognize(pair(line_ending, line_ending)))(input)
+}
+
+/// Sections of input separated by blank lines, all parsed by `section`
+pub fn sections<'a, O>(
+    section: impl Parser<&'a str, O, VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> PResult<'a, Vec<O>> {
+    separated_list1(blank_line, section)
+}
+
+/// Two differently shaped sections separated by a blank line
+pub fn section_pair<'a, A, B>(
+    first: impl Parser<&'a str, A, VerboseError<&'a str>>,
+    second: impl Parser<&'a str, B, VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> PResult<'a, (A, B)> {
+    separated_pair(first, blank_line, cut(second))
+}
+
+/// A rectangular block of characters, converted with `cell`
+pub fn grid<'a, T>(
+    cell: impl Fn(char) -> Option<T>,
+) -> impl FnMut(&'a str) -> PResult<'a, Grid<T>> {
+    move |input: &'a str| {
+        let row = context("a grid row", take_till1(|c| c == '\r' || c == '\n'));
+        let (rest, rows) = lines_of(row)(input)?;
+
+        let width = rows[0].chars().count();
+        let mut cells = Vec::with_capacity(width * rows.len());
+        for row in &rows {
+            for (idx, c) in row.char_indices() {
+                if idx > 0 && cells.len() % width == 0 {
+                    return Err(failure(&row[idx..], "end of the row"));
+                }
+                cells.push(cell(c).ok_or_else(|| failure(&row[idx..], "a grid cell"))?);
+            }
+            if cells.len() % width != 0 {
+                return Err(failure(&row[row.len()..], "more cells in the row"));
+            }
+        }
+
+        let grid = Grid::new(width, rows.len(), cells).expect("every row has the same width");
+        Ok((rest, grid))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error_at<O: std::fmt::Debug>(result: Result<O, Diagnostic>) -> (usize, usize, String) {
+        let err = result.unwrap_err();
+        (err.line, err.column, err.expected)
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(parse_all(unsigned::<u32>, "42"), Ok(42));
+        assert_eq!(parse_all(signed::<i64>, "-17"), Ok(-17));
+        assert_eq!(
+            error_at(parse_all(unsigned::<u8>, "x")),
+            (1, 1, "an unsigned number".to_string())
+        );
+        assert!(parse_all(unsigned::<u8>, "300").is_err());
+    }
+
+    #[test]
+    fn lists_and_key_values() {
+        assert_eq!(
+            parse_all(list(',', unsigned::<u32>), "75,47,61"),
+            Ok(vec![75, 47, 61])
+        );
+        assert_eq!(
+            parse_all(
+                key_value(unsigned::<u64>, spaced(unsigned::<u64>)),
+                "190: 10 19"
+            ),
+            Ok((190, vec![10, 19]))
+        );
+        assert_eq!(
+            error_at(parse_all(list(',', unsigned::<u32>), "75,x")),
+            (1, 4, "an unsigned number".to_string())
+        );
+    }
+
+    #[test]
+    fn lines_and_sections() {
+        let input = "1|2\n3|4\n\n5,6\n7\n";
+        let rule = separated_pair(unsigned::<u32>, char('|'), unsigned::<u32>);
+        let (rules, updates) = parse_all(
+            section_pair(lines_of(rule), lines_of(list(',', unsigned::<u32>))),
+            input,
+        )
+        .unwrap();
+
+        assert_eq!(rules, [(1, 2), (3, 4)]);
+        assert_eq!(updates, [vec![5, 6], vec![7]]);
+
+        assert_eq!(
+            parse_all(sections(lines_of(unsigned::<u32>)), "1\n2\n\n3"),
+            Ok(vec![vec![1, 2], vec![3]])
+        );
+    }
+
+    #[test]
+    fn lines_report_the_broken_line() {
+        let numbers = lines_of(spaced(unsigned::<u32>));
+        assert_eq!(
+            error_at(parse_all(numbers, "1 2\n3 4\n5 ?\n")),
+            (3, 3, "an unsigned number".to_string())
+        );
+        assert_eq!(
+            error_at(parse_all(lines_of(unsigned::<u32>), "1\nx\n")),
+            (2, 1, "an unsigned number".to_string())
+        );
+    }
+
+    #[test]
+    fn grids() {
+        let walls = |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        };
+
+        let parsed = parse_all(grid(walls), "#..\n.#.\n").unwrap();
+        assert_eq!((parsed.width(), parsed.height()), (3, 2));
+        assert!(parsed[(1, 1)]);
+
+        assert_eq!(
+            error_at(parse_all(grid(walls), "#..\n.x.\n")),
+            (2, 2, "a grid cell".to_string())
+        );
+        assert_eq!(
+            error_at(parse_all(grid(walls), "#..\n.#\n")),
+            (2, 3, "more cells in the row".to_string())
+        );
+        assert_eq!(
+            error_at(parse_all(grid(walls), "#..\n.#..\n")),
+            (2, 4, "end of the row".to_string())
+        );
+    }
+}
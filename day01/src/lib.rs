@@ -1,8 +1,9 @@
-use aoc_day::AoCDay;
+use aoc_day::{Answer, AoCDay, AoCResult, Example};
+use aoc_parse::{lines_of, parse_all, signed, PResult};
+use nom::character::complete::space1;
+use nom::error::context;
+use nom::sequence::separated_pair;
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::path::Path;
 
 #[derive(Default)]
 pub struct Day01 {
@@ -11,14 +12,11 @@ pub struct Day01 {
 }
 
 impl Day01 {
-    fn parse_input_line(line: &str) -> (i64, i64) {
-        let ids: Vec<i64> = line
-            .split_whitespace()
-            .filter(|s| !s.is_empty())
-            .take(2)
-            .filter_map(|s| s.parse().ok())
-            .collect();
-        (ids[0], ids[1])
+    fn parse_input_line(line: &str) -> PResult<'_, (i64, i64)> {
+        context(
+            "two location IDs separated by spaces",
+            separated_pair(signed, space1, signed),
+        )(line)
     }
 
     fn total_distance(&self) -> u64 {
@@ -48,24 +46,20 @@ impl Day01 {
 }
 
 impl AoCDay for Day01 {
-    fn part1(&mut self) {
+    fn part1(&mut self) -> AoCResult<Answer> {
         let result = self.total_distance();
-        println!("Total distance: {}", result);
+        Ok(Answer::new("Total distance", result))
     }
 
-    fn part2(&mut self) {
+    fn part2(&mut self) -> AoCResult<Answer> {
         let result = self.similarity_score();
-        println!("Similarity score: {}", result);
+        Ok(Answer::new("Similarity score", result))
     }
 
-    fn load_input(&mut self, path: &Path) -> anyhow::Result<()> {
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
-        let (left, right) = reader
-            .lines()
-            .map_while(Result::ok)
-            .map(|line| Self::parse_input_line(&line))
-            .collect();
+    fn load_str(&mut self, input: &str) -> AoCResult<()> {
+        let (left, right) = parse_all(lines_of(Self::parse_input_line), input)?
+            .into_iter()
+            .unzip();
 
         self.left_ids = left;
         self.right_ids = right;
@@ -74,9 +68,16 @@ impl AoCDay for Day01 {
     }
 }
 
+aoc_day::register_day!(1, "Historian Hysteria", Day01, examples: [
+    Example::new("example", include_str!("../example_input.txt"))
+        .part1("11")
+        .part2("31"),
+]);
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_day::AoCError;
     use std::path::PathBuf;
     use std::sync::LazyLock;
 
@@ -89,11 +90,23 @@ mod tests {
     fn parse_input_line_test() {
         let line = EXAMPLE_INPUT.lines().next().unwrap();
         let expected = (3, 4);
-        let actual = Day01::parse_input_line(line);
+        let (_, actual) = Day01::parse_input_line(line).unwrap();
 
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn parse_missing_id_test() {
+        let mut day = Day01::default();
+
+        let Err(AoCError::Parse(diagnostic)) = day.load_str("3   4\n4\n2   5\n") else {
+            panic!("line without a right ID was accepted");
+        };
+
+        assert_eq!((diagnostic.line, diagnostic.column), (2, 2));
+        assert_eq!(diagnostic.expected, "two location IDs separated by spaces");
+    }
+
     #[test]
     fn parse_input_test() {
         let mut day = Day01::default();
@@ -1,8 +1,7 @@
-use aoc_day::AoCDay;
+use aoc_day::{Answer, AoCDay, AoCResult, Example};
+use aoc_parse::{lines_of, parse_all, signed, spaced};
+use nom::error::context;
 use std::fmt::Debug;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::path::Path;
 use std::str::FromStr;
 
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
@@ -31,9 +30,8 @@ impl FromStr for Report {
 
 impl Report {
     fn levels_are_safe(levels: &[i64]) -> bool {
-        let mut index = 0;
         let mut prev_direction: Option<Direction> = None;
-        for pair in levels.windows(2) {
+        for (index, pair) in levels.windows(2).enumerate() {
             let val0 = (pair[0], index);
             let val1 = (pair[1], index + 1);
             let diff = val1.0 - val0.0;
@@ -55,7 +53,6 @@ impl Report {
             }
 
             prev_direction = Some(direction);
-            index += 1;
         }
 
         true
@@ -120,30 +117,33 @@ impl Day02 {
 }
 
 impl AoCDay for Day02 {
-    fn part1(&mut self) {
+    fn part1(&mut self) -> AoCResult<Answer> {
         let result = self.count_safe_reports();
-        println!("Safe reports: {}", result);
+        Ok(Answer::new("Safe reports", result))
     }
 
-    fn part2(&mut self) {
+    fn part2(&mut self) -> AoCResult<Answer> {
         let result = self.count_safe_reports2();
-        println!("Safe reports: {}", result);
+        Ok(Answer::new("Safe reports", result))
     }
 
-    fn load_input(&mut self, path: &Path) -> anyhow::Result<()> {
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
-
-        self.reports = reader
-            .lines()
-            .map_while(Result::ok)
-            .filter_map(|line| line.parse().ok())
+    fn load_str(&mut self, input: &str) -> AoCResult<()> {
+        let reports = context("a report of levels separated by spaces", spaced(signed));
+        self.reports = parse_all(lines_of(reports), input)?
+            .into_iter()
+            .map(|levels| Report { levels })
             .collect();
 
         Ok(())
     }
 }
 
+aoc_day::register_day!(2, "Red-Nosed Reports", Day02, examples: [
+    Example::new("example", include_str!("../example_input.txt"))
+        .part1("2")
+        .part2("4"),
+]);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,18 +2,11 @@ use nom::character::complete;
 use nom::character::complete::anychar;
 use nom::multi::many_till;
 use nom::sequence::separated_pair;
-use nom::{branch::alt, bytes::complete::tag, multi::many1, sequence::delimited, IResult, Parser};
-use std::{fs, sync::LazyLock};
+use nom::{
+    branch::alt, bytes::complete::tag, multi::many1, sequence::delimited, IResult, Offset, Parser,
+};
 
-use aoc_day::AoCDay;
-use regex::{Regex, RegexBuilder};
-
-static INSTRUCTION_REGEX: LazyLock<Regex> = LazyLock::new(|| {
-    RegexBuilder::new(r"(?:(?P<name>(?:mul|do|don't))\((?P<args>(?:\d+,*)*)\))")
-        .case_insensitive(true)
-        .build()
-        .expect("INSTRUCTION_REGEX failed to compile")
-});
+use aoc_day::{Answer, AoCDay, AoCResult, Diagnostic, Example};
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 enum Instruction {
@@ -75,27 +68,30 @@ impl Day03 {
 }
 
 impl AoCDay for Day03 {
-    fn part1(&mut self) {
+    fn part1(&mut self) -> AoCResult<Answer> {
         let sum = self.sum_mults();
-        println!("Sum of mults: {sum}");
+        Ok(Answer::new("Sum of mults", sum))
     }
 
-    fn part2(&mut self) {
+    fn part2(&mut self) -> AoCResult<Answer> {
         let sum = self.sum_mults2();
-        println!("Sum of mults: {sum}");
+        Ok(Answer::new("Sum of mults", sum))
     }
 
-    fn load_input(&mut self, path: &std::path::Path) -> anyhow::Result<()> {
-        let input = fs::read_to_string(path)?;
-
-        let (_rem, instructions) = parse_instructions(&input)?;
-
-        self.instructions = instructions;
+    fn load_str(&mut self, input: &str) -> AoCResult<()> {
+        self.instructions = parse_instructions(input)?;
 
         Ok(())
     }
 }
 
+aoc_day::register_day!(3, "Mull It Over", Day03, examples: [
+    Example::new("example", include_str!("../example_input.txt")).part1("161"),
+    Example::new("example2", include_str!("../example_input2.txt"))
+        .part1("161")
+        .part2("48"),
+]);
+
 fn mul_instruction(i: &str) -> IResult<&str, Instruction> {
     let (input, _) = tag("mul")(i)?;
     let (input, pair): (&str, (u64, u64)) = delimited(
@@ -119,18 +115,27 @@ fn dont_instruction(i: &str) -> IResult<&str, Instruction> {
     Ok((remainder, Instruction::Dont))
 }
 
-fn parse_instructions(i: &str) -> IResult<(), Vec<Instruction>> {
+/// Every instruction in `i`, skipping the corrupted memory around them
+///
+/// Memory without a single instruction in it is an error, reported where the search gave up.
+fn parse_instructions(i: &str) -> Result<Vec<Instruction>, Diagnostic> {
     let instruction = alt((mul_instruction, do_instruction, dont_instruction));
 
-    let (_, instructions) =
-        many1(many_till(anychar, instruction).map(|(_discard, ins)| ins))(i).unwrap();
-
-    Ok(((), instructions))
+    match many1(many_till(anychar, instruction).map(|(_discard, ins)| ins))(i) {
+        Ok((_, instructions)) => Ok(instructions),
+        Err(nom::Err::Error(err) | nom::Err::Failure(err)) => Err(Diagnostic::at(
+            i,
+            i.offset(err.input),
+            "a mul(X,Y), do() or don't() instruction",
+        )),
+        Err(nom::Err::Incomplete(_)) => Err(Diagnostic::at(i, i.len(), "more input")),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::path::PathBuf;
+    use std::sync::LazyLock;
 
     use super::*;
 
@@ -161,7 +166,7 @@ mod tests {
             Instruction::Mul(11, 8),
             Instruction::Mul(8, 5),
         ];
-        let (_, actual) = parse_instructions(EXAMPLE1).unwrap();
+        let actual = parse_instructions(EXAMPLE1).unwrap();
 
         assert_eq!(actual, expected);
     }
@@ -197,10 +202,21 @@ mod tests {
             Instruction::Mul(8, 5),
         ];
 
-        let (_, actual) = parse_instructions(EXAMPLE2).unwrap();
+        let actual = parse_instructions(EXAMPLE2).unwrap();
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn no_instructions_test() {
+        let err = parse_instructions("mul(1,x)\ndo(").unwrap_err();
+
+        assert_eq!((err.line, err.column), (2, 4));
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 4: expected a mul(X,Y), do() or don't() instruction, found end of input"
+        );
+    }
+
     #[test]
     fn sum_mults_test() {
         let mut day = Day03::default();
@@ -1,57 +1,37 @@
+use aoc_day::{Answer, AoCResult, Diagnostic, Example, Solution, SolutionDay};
+use aoc_grid::{Direction8, Grid, Position};
+use aoc_parse::{grid, parse_all};
+use std::collections::HashSet;
 use std::hash::Hash;
 use std::str::FromStr;
+use trie::Trie;
 
-#[derive(Default, Copy, Clone, Eq, PartialEq, Debug, Hash)]
-enum Direction {
-    #[default]
-    North,
-    NorthEast,
-    East,
-    SouthEast,
-    South,
-    SouthWest,
-    West,
-    NorthWest,
-}
+mod trie;
 
+/// An occurrence of [Crossword::XMAS], read from the `X` at `start_position` in `direction`
 #[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
-struct CrosswordMatch {
-    start_position: (usize, usize),
-    direction: Direction,
+pub struct CrosswordMatch {
+    start_position: Position,
+    direction: Direction8,
+}
+
+/// A dictionary word found in a [Crossword], read from `start_position` in `direction`
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct WordMatch<'w> {
+    pub word: &'w str,
+    pub start_position: Position,
+    pub direction: Direction8,
 }
 
-// impl Hash for CrosswordMatch {
-//     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-//         self.positions
-//             .iter()
-//             .for_each(|pos| state.write_usize(pos.0 ^ pos.1));
-//     }
-// }
-
-struct Crossword {
-    cells: Vec<char>,
-    width: usize,
-    height: usize,
+pub struct Crossword {
+    letters: Grid<char>,
 }
 
 impl FromStr for Crossword {
-    type Err = ();
+    type Err = Diagnostic;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut width = 0;
-        let mut height = 0;
-        let cells = s
-            .lines()
-            .flat_map(|line| {
-                if width == 0 {
-                    width = line.len();
-                }
-                height += 1;
-                line.chars()
-            })
-            .collect();
-
-        Ok(Crossword::new(cells, width, height))
+        Ok(Crossword::new(parse_all(grid(Some), s)?))
     }
 }
 
@@ -59,19 +39,139 @@ impl Crossword {
     const XMAS: &'static str = "XMAS";
     const SAMX: &'static str = "SAMX";
 
-    fn new(cells: Vec<char>, width: usize, height: usize) -> Self {
-        Self {
-            cells,
-            width,
-            height,
+    fn new(letters: Grid<char>) -> Self {
+        Self { letters }
+    }
+
+    fn read_at(&self, pos: Position, direction: Direction8) -> Option<char> {
+        let pos = self.letters.offset(pos, direction.delta())?;
+        Some(self.letters[pos])
+    }
+
+    /// Whether `word` can be read starting at `start` and heading in `direction`
+    fn reads(&self, word: &str, start: Position, direction: Direction8) -> bool {
+        let mut letters = self.letters.ray(start, direction).map(|(_, c)| *c);
+        word.chars()
+            .all(|expected| letters.next() == Some(expected))
+    }
+
+    /// Every occurrence of [Self::XMAS], in any of the eight directions
+    ///
+    /// A word spelled backwards is found as [Self::SAMX] from its far end and recorded from its
+    /// `X`, so each occurrence is only counted once however it was found.
+    fn find_xmas(&self) -> HashSet<CrosswordMatch> {
+        let last_letter = Self::XMAS.len() - 1;
+        let mut matches = HashSet::new();
+
+        for start in self.letters.positions() {
+            for direction in Direction8::ALL {
+                if self.reads(Self::XMAS, start, direction) {
+                    matches.insert(CrosswordMatch {
+                        start_position: start,
+                        direction,
+                    });
+                } else if self.reads(Self::SAMX, start, direction) {
+                    let (x_position, _) = self
+                        .letters
+                        .ray(start, direction)
+                        .nth(last_letter)
+                        .expect("SAMX was read along this ray");
+                    matches.insert(CrosswordMatch {
+                        start_position: x_position,
+                        direction: direction.reverse(),
+                    });
+                }
+            }
+        }
+
+        matches
+    }
+
+    /// Every occurrence of any of `words`, in any of the eight directions
+    ///
+    /// The words are searched for together, so each cell is only read as far as the longest word
+    /// which could still match, however many words there are. Matches are ordered by start
+    /// position in row order, then by direction clockwise from [Direction8::North], then by
+    /// length. A word is reported once for every direction it can be read in, so palindromes and
+    /// single letters appear more than once at the same position.
+    pub fn find_words<'w>(&self, words: impl IntoIterator<Item = &'w str>) -> Vec<WordMatch<'w>> {
+        let trie = Trie::new(words);
+        let mut matches = Vec::new();
+
+        for start in self.letters.positions() {
+            for direction in Direction8::ALL {
+                let mut node = Trie::ROOT;
+                for (_, &c) in self.letters.ray(start, direction) {
+                    let Some(next) = trie.step(node, c) else {
+                        break;
+                    };
+                    node = next;
+
+                    if let Some(word) = trie.word(node) {
+                        matches.push(WordMatch {
+                            word,
+                            start_position: start,
+                            direction,
+                        });
+                    }
+                }
+            }
         }
+
+        matches
+    }
+
+    /// Whether `centre` is the `A` where two diagonal `MAS`es cross, in either orientation
+    fn is_x_mas(&self, centre: Position) -> bool {
+        let is_mas = |direction: Direction8| {
+            matches!(
+                (
+                    self.read_at(centre, direction),
+                    self.read_at(centre, direction.reverse())
+                ),
+                (Some('M'), Some('S')) | (Some('S'), Some('M'))
+            )
+        };
+
+        self.letters[centre] == 'A'
+            && is_mas(Direction8::NorthWest)
+            && is_mas(Direction8::NorthEast)
+    }
+
+    fn x_mas_count(&self) -> usize {
+        self.letters
+            .positions()
+            .filter(|&pos| self.is_x_mas(pos))
+            .count()
+    }
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Crossword;
+
+    fn parse(input: &str) -> AoCResult<Crossword> {
+        Ok(Crossword::from_str(input)?)
+    }
+
+    fn part1(crossword: &Crossword) -> AoCResult<Answer> {
+        let count = crossword.find_xmas().len();
+        Ok(Answer::new("XMAS occurrences", count))
     }
 
-    fn read_at(&self, x: usize, y: usize) -> Option<char> {
-        self.cells.get(x + (y * self.width)).copied()
+    fn part2(crossword: &Crossword) -> AoCResult<Answer> {
+        let count = crossword.x_mas_count();
+        Ok(Answer::new("X-MAS occurrences", count))
     }
 }
 
+aoc_day::register_day!(4, "Ceres Search", SolutionDay<Day04>, examples: [
+    Example::new("example", include_str!("../example_input.txt"))
+        .part1("18")
+        .part2("9"),
+]);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -88,4 +188,78 @@ mod tests {
         let hash = hasher.finish();
         assert_ne!(hash, 0);
     }
+
+    #[test]
+    fn ragged_rows_are_located() {
+        let Err(err) = Crossword::from_str("XMAS\nSAM\n") else {
+            panic!("parsed a ragged crossword");
+        };
+
+        assert_eq!((err.line, err.column), (2, 4));
+        assert_eq!(err.expected, "more cells in the row");
+    }
+
+    #[test]
+    fn backwards_words_start_at_x() {
+        let crossword = Crossword::from_str("SAMX").unwrap();
+
+        let matches = crossword.find_xmas();
+        let expected = CrosswordMatch {
+            start_position: (3, 0),
+            direction: Direction8::West,
+        };
+        assert_eq!(matches, HashSet::from([expected]));
+    }
+
+    #[test]
+    fn find_xmas_example() {
+        let crossword = Crossword::from_str(EXAMPLE_INPUT).unwrap();
+
+        assert_eq!(crossword.find_xmas().len(), 18);
+    }
+
+    #[test]
+    fn find_words_example() {
+        let crossword = Crossword::from_str(EXAMPLE_INPUT).unwrap();
+
+        let matches = crossword.find_words([Crossword::XMAS, Crossword::SAMX, "MAS"]);
+        let count = |word| matches.iter().filter(|m| m.word == word).count();
+
+        assert_eq!(count(Crossword::XMAS), 18);
+        assert_eq!(count(Crossword::SAMX), 18);
+        assert!(count("MAS") > count(Crossword::XMAS));
+    }
+
+    #[test]
+    fn find_words_reports_every_word() {
+        let crossword = Crossword::from_str("CAT\nARE\nTEA").unwrap();
+        let words = ["CAT", "CA", "TEA", "DOG"];
+
+        let matches = crossword.find_words(words.iter().copied());
+        let found = |word, start_position, direction| WordMatch {
+            word,
+            start_position,
+            direction,
+        };
+
+        assert_eq!(
+            matches,
+            [
+                found("CA", (0, 0), Direction8::East),
+                found("CAT", (0, 0), Direction8::East),
+                found("CA", (0, 0), Direction8::South),
+                found("CAT", (0, 0), Direction8::South),
+                found("TEA", (2, 0), Direction8::South),
+                found("TEA", (0, 2), Direction8::East),
+            ]
+        );
+        assert!(crossword.find_words([]).is_empty());
+    }
+
+    #[test]
+    fn x_mas_count_example() {
+        let crossword = Crossword::from_str(EXAMPLE_INPUT).unwrap();
+
+        assert_eq!(crossword.x_mas_count(), 9);
+    }
 }
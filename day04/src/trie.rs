@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+
+/// A prefix tree over a dictionary of words, walked one letter at a time
+///
+/// Following a path of letters costs the same however many words share it, so reading along the
+/// grid only needs to go as deep as the longest word.
+#[derive(Debug)]
+pub(crate) struct Trie<'w> {
+    nodes: Vec<TrieNode<'w>>,
+}
+
+#[derive(Debug, Default)]
+struct TrieNode<'w> {
+    children: HashMap<char, usize>,
+    /// The dictionary word which ends at this node, if any
+    word: Option<&'w str>,
+}
+
+impl<'w> Trie<'w> {
+    /// Index of the node for the empty prefix
+    pub(crate) const ROOT: usize = 0;
+
+    /// Builds a trie of `words`; empty and repeated words are ignored
+    pub(crate) fn new(words: impl IntoIterator<Item = &'w str>) -> Self {
+        let mut trie = Self {
+            nodes: vec![TrieNode::default()],
+        };
+
+        for word in words.into_iter().filter(|word| !word.is_empty()) {
+            let mut node = Self::ROOT;
+            for c in word.chars() {
+                node = match trie.nodes[node].children.get(&c) {
+                    Some(&child) => child,
+                    None => {
+                        trie.nodes.push(TrieNode::default());
+                        let child = trie.nodes.len() - 1;
+                        trie.nodes[node].children.insert(c, child);
+                        child
+                    }
+                };
+            }
+            trie.nodes[node].word = Some(word);
+        }
+
+        trie
+    }
+
+    /// The node reached by reading `c` from `node`, if any word continues that way
+    pub(crate) fn step(&self, node: usize, c: char) -> Option<usize> {
+        self.nodes[node].children.get(&c).copied()
+    }
+
+    /// The word spelled by the path to `node`, if it is in the dictionary
+    pub(crate) fn word(&self, node: usize) -> Option<&'w str> {
+        self.nodes[node].word
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup<'w>(trie: &Trie<'w>, text: &str) -> Option<&'w str> {
+        let node = text
+            .chars()
+            .try_fold(Trie::ROOT, |node, c| trie.step(node, c))?;
+        trie.word(node)
+    }
+
+    #[test]
+    fn shared_prefixes() {
+        let trie = Trie::new(["XMAS", "XM", "MAS", "", "XM"]);
+
+        assert_eq!(lookup(&trie, "XMAS"), Some("XMAS"));
+        assert_eq!(lookup(&trie, "XM"), Some("XM"));
+        assert_eq!(lookup(&trie, "MAS"), Some("MAS"));
+        assert_eq!(lookup(&trie, "XMA"), None);
+        assert_eq!(lookup(&trie, "SAM"), None);
+        assert_eq!(lookup(&trie, ""), None);
+        assert_eq!(trie.nodes.len(), 8);
+    }
+}
@@ -6,7 +6,7 @@ const INPUT: &str = include_str!("../example_input.txt");
 fn input_parsing_benchmark(c: &mut Criterion) {
     c.bench_function("input_into_updates", |b| {
         b.iter(|| {
-            black_box(day05::parse::input_to_updates(INPUT));
+            black_box(day05::parse::input_to_updates(INPUT).unwrap());
         })
     });
 }
@@ -1,8 +1,7 @@
-use aoc_day::AoCDay;
+use aoc_day::{Answer, AoCDay, AoCResult, Example};
 use std::cell::RefCell;
 use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
-use std::path::Path;
 use std::rc::Rc;
 
 #[derive(Default, Clone)]
@@ -139,25 +138,29 @@ impl Day05 {
 }
 
 impl AoCDay for Day05 {
-    fn part1(&mut self) {
+    fn part1(&mut self) -> AoCResult<Answer> {
         let sum = self.sum_middle_numbers_sorted();
-        println!("Sum of middle numbers (sorted): {}", sum);
+        Ok(Answer::new("Sum of middle numbers (sorted)", sum))
     }
 
-    fn part2(&mut self) {
+    fn part2(&mut self) -> AoCResult<Answer> {
         let sum = self.sum_middle_numbers_unsorted();
-        println!("Sum of middle numbers (unsorted): {}", sum);
+        Ok(Answer::new("Sum of middle numbers (unsorted)", sum))
     }
 
-    fn load_input(&mut self, path: &Path) -> anyhow::Result<()> {
-        let text = std::fs::read_to_string(path)?;
-
-        self.updates = parse::input_to_updates(&text);
+    fn load_str(&mut self, input: &str) -> AoCResult<()> {
+        self.updates = parse::input_to_updates(input)?;
 
         Ok(())
     }
 }
 
+aoc_day::register_day!(5, "Print Queue", Day05, examples: [
+    Example::new("example", include_str!("../example_input.txt"))
+        .part1("143")
+        .part2("123"),
+]);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -166,7 +169,7 @@ mod tests {
 
     #[test]
     fn update_is_sorted() {
-        let updates = parse::input_to_updates(TEST_INPUT);
+        let updates = parse::input_to_updates(TEST_INPUT).unwrap();
 
         let expected_sort = [true, true, true, false, false, false];
 
@@ -178,7 +181,7 @@ mod tests {
 
     #[test]
     fn part1_test() {
-        let updates = parse::input_to_updates(TEST_INPUT);
+        let updates = parse::input_to_updates(TEST_INPUT).unwrap();
         let day05 = Day05 { updates };
 
         assert_eq!(day05.sum_middle_numbers_sorted(), 143);
@@ -186,7 +189,7 @@ mod tests {
 
     #[test]
     fn part2_test() {
-        let updates = parse::input_to_updates(TEST_INPUT);
+        let updates = parse::input_to_updates(TEST_INPUT).unwrap();
         let day05 = Day05 { updates };
 
         assert_eq!(day05.sum_middle_numbers_unsorted(), 123);
@@ -195,18 +198,22 @@ mod tests {
 
 pub mod parse {
     use super::{OrderingRules, Page, Update};
+    use aoc_day::Diagnostic;
+    use aoc_parse::{lines_of, list, parse_all, section_pair, unsigned, PResult};
     use nom::character::complete;
-    use nom::multi::{many1, separated_list1};
-    use nom::sequence::{separated_pair, terminated};
-    use nom::IResult;
+    use nom::error::context;
+    use nom::sequence::separated_pair;
     use std::collections::{HashMap, HashSet};
 
-    fn single_order_rule(input: &str) -> IResult<&str, (u32, u32)> {
-        separated_pair(complete::u32, complete::char('|'), complete::u32)(input)
+    fn single_order_rule(input: &str) -> PResult<'_, (u32, u32)> {
+        context(
+            "an ordering rule such as 47|53",
+            separated_pair(unsigned, complete::char('|'), unsigned),
+        )(input)
     }
 
-    fn order_rules(input: &str) -> IResult<&str, OrderingRules> {
-        let (input, rules) = many1(terminated(single_order_rule, complete::char('\n')))(input)?;
+    fn order_rules(input: &str) -> PResult<'_, OrderingRules> {
+        let (input, rules) = lines_of(single_order_rule)(input)?;
 
         let mut order_map = HashMap::new();
         for rule in rules {
@@ -217,19 +224,16 @@ pub mod parse {
         Ok((input, OrderingRules::new(order_map)))
     }
 
-    fn update_pages(input: &str) -> IResult<&str, Vec<u32>> {
-        let (input, pages) = separated_list1(complete::char(','), complete::u32)(input)?;
-
-        Ok((input, pages))
+    fn update_pages(input: &str) -> PResult<'_, Vec<u32>> {
+        list(',', unsigned)(input)
     }
 
-    fn updates(input: &str) -> IResult<&str, Vec<Vec<u32>>> {
-        many1(terminated(update_pages, complete::char('\n')))(input)
+    fn updates(input: &str) -> PResult<'_, Vec<Vec<u32>>> {
+        lines_of(update_pages)(input)
     }
 
-    pub fn input_to_updates(input: &str) -> Vec<Update> {
-        let (_input, parsed_vals) =
-            separated_pair(order_rules, complete::char('\n'), updates)(input).unwrap();
+    pub fn input_to_updates(input: &str) -> Result<Vec<Update>, Diagnostic> {
+        let parsed_vals = parse_all(section_pair(order_rules, updates), input)?;
 
         let updates = parsed_vals
             .1
@@ -251,7 +255,7 @@ pub mod parse {
             })
             .collect::<Vec<Update>>();
 
-        updates
+        Ok(updates)
     }
 
     #[cfg(test)]
@@ -269,9 +273,19 @@ pub mod parse {
 
         #[test]
         fn rules_and_updates_test() {
-            let updates = input_to_updates(TEST_INPUT);
+            let updates = input_to_updates(TEST_INPUT).unwrap();
 
             assert!(!updates.is_empty());
         }
+
+        #[test]
+        fn malformed_update_is_an_error() {
+            let Err(err) = input_to_updates("47|53\n\n75,47,x\n") else {
+                panic!("malformed update was accepted");
+            };
+
+            assert_eq!((err.line, err.column), (3, 7));
+            assert_eq!(err.expected, "an unsigned number");
+        }
     }
 }
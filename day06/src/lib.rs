@@ -1,44 +1,33 @@
-use aoc_day::AoCDay;
+use aoc_day::{Answer, AoCResult, Diagnostic, Example, Solution, SolutionDay};
+use aoc_grid::{Direction4, Grid, Point};
+use aoc_parse::{grid, parse_all};
 use std::collections::HashSet;
 use std::fmt::Display;
-use std::path::Path;
 use std::str::FromStr;
 
 type Position = (usize, usize);
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
-enum Direction {
-    #[default]
-    North,
-    East,
-    South,
-    West,
-}
-
-impl TryFrom<char> for Direction {
-    type Error = String;
-
-    fn try_from(value: char) -> Result<Self, Self::Error> {
-        match value {
-            '^' => Ok(Direction::North),
-            '>' => Ok(Direction::East),
-            'v' => Ok(Direction::South),
-            '<' => Ok(Direction::West),
-            _ => Err(format!("{value} is not a valid direction")),
-        }
+/// The [Direction4] a guard drawn as `c` is facing, if `c` is a guard
+fn guard_direction(c: char) -> Option<Direction4> {
+    match c {
+        '^' => Some(Direction4::North),
+        '>' => Some(Direction4::East),
+        'v' => Some(Direction4::South),
+        '<' => Some(Direction4::West),
+        _ => None,
     }
 }
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 struct Guard {
     current_position: Position,
-    direction: Direction,
+    direction: Direction4,
     traveled_distance: u64,
     distinct_positions: HashSet<Position>,
 }
 
 impl Guard {
-    fn new(position: Position, direction: Direction) -> Self {
+    fn new(position: Position, direction: Direction4) -> Self {
         Self {
             current_position: position,
             direction,
@@ -48,60 +37,44 @@ impl Guard {
     }
 
     fn turn_right(&mut self) {
-        self.direction = match self.direction {
-            Direction::North => Direction::East,
-            Direction::East => Direction::South,
-            Direction::South => Direction::West,
-            Direction::West => Direction::North,
-        };
+        self.direction = self.direction.turn_right();
     }
 
     fn move_to(&mut self, obstacle: &Option<Obstacle>, max_width: usize, max_height: usize) {
         let (cur_x, cur_y) = self.current_position;
+        // The guard stops on the cell just before an obstacle, which is always ahead of them
+        let stop = obstacle.map(|obstacle| {
+            (Point::from(obstacle.position) - self.direction.delta())
+                .to_position()
+                .expect("obstacles are never behind the guard")
+        });
         let (final_position, visited_cells): (Position, Vec<Position>) = match self.direction {
-            Direction::North => {
-                let target_pos = if let Some(obstacle) = obstacle {
-                    (cur_x, obstacle.position.1 + 1)
-                } else {
-                    (cur_x, 0)
-                };
+            Direction4::North => {
+                let target_pos = stop.unwrap_or((cur_x, 0));
                 let visited_cells = (target_pos.1..self.current_position.1)
                     .map(|y| (cur_x, y))
                     .collect();
 
                 (target_pos, visited_cells)
             }
-            Direction::East => {
-                let target_pos = if let Some(obstacle) = obstacle {
-                    (obstacle.position.0 - 1, cur_y)
-                } else {
-                    (max_width, cur_y)
-                };
+            Direction4::East => {
+                let target_pos = stop.unwrap_or((max_width, cur_y));
                 let visited_cells = (self.current_position.0 + 1..=target_pos.0)
                     .map(|x| (x, cur_y))
                     .collect();
 
                 (target_pos, visited_cells)
             }
-            Direction::South => {
-                let target_pos = if let Some(obstacle) = obstacle {
-                    (cur_x, obstacle.position.1 - 1)
-                } else {
-                    (cur_x, max_height)
-                };
-
+            Direction4::South => {
+                let target_pos = stop.unwrap_or((cur_x, max_height));
                 let visited_cells = (self.current_position.1 + 1..=target_pos.1)
                     .map(|y| (cur_x, y))
                     .collect();
 
                 (target_pos, visited_cells)
             }
-            Direction::West => {
-                let target_pos = if let Some(obstacle) = obstacle {
-                    (obstacle.position.0 + 1, cur_y)
-                } else {
-                    (0, cur_y)
-                };
+            Direction4::West => {
+                let target_pos = stop.unwrap_or((0, cur_y));
                 let visited_cells = (target_pos.0..self.current_position.0)
                     .map(|x| (x, cur_y))
                     .collect();
@@ -118,7 +91,7 @@ impl Guard {
         self.traveled_distance += distance_traveled as u64;
     }
 
-    /// Casts a "ray" from the [Guard]'s current position and [Direction].
+    /// Casts a "ray" from the [Guard]'s current position and [Direction4].
     ///
     /// # Return
     ///
@@ -129,16 +102,16 @@ impl Guard {
         let obstacle_iter = obstacles.iter();
 
         let hit = match self.direction {
-            Direction::North => obstacle_iter
+            Direction4::North => obstacle_iter
                 .filter(|obs| obs.position.1 < cur_y && obs.position.0 == cur_x)
                 .max_by_key(|obs| obs.position.1),
-            Direction::East => obstacle_iter
+            Direction4::East => obstacle_iter
                 .filter(|obs| obs.position.0 > cur_x && obs.position.1 == cur_y)
                 .min_by_key(|obs| obs.position.0),
-            Direction::South => obstacle_iter
+            Direction4::South => obstacle_iter
                 .filter(|obs| obs.position.1 > cur_y && obs.position.0 == cur_x)
                 .min_by_key(|obs| obs.position.1),
-            Direction::West => obstacle_iter
+            Direction4::West => obstacle_iter
                 .filter(|obs| obs.position.0 < cur_x && obs.position.1 == cur_y)
                 .max_by_key(|obs| obs.position.0),
         };
@@ -163,6 +136,30 @@ impl Guard {
             false
         }
     }
+
+    /// Patrols until leaving the patrol area or repeating a turn
+    ///
+    /// A [Guard] which turns at the same position to face the same [Direction4] twice will follow
+    /// the same route forever.
+    ///
+    /// # Return
+    ///
+    /// `true` if the [Guard] is stuck in a loop; `false` if they leave the patrol area.
+    fn patrol_loops(
+        &mut self,
+        obstacles: &[Obstacle],
+        max_width: usize,
+        max_height: usize,
+    ) -> bool {
+        let mut turns = HashSet::new();
+        while self.patrol(obstacles, max_width, max_height) {
+            if !turns.insert((self.current_position, self.direction)) {
+                return true;
+            }
+        }
+
+        false
+    }
 }
 
 /*
@@ -187,7 +184,7 @@ impl Obstacle {
     }
 }
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct PatrolArea {
     guard: Guard,
     obstacles: Vec<Obstacle>,
@@ -200,49 +197,39 @@ impl PatrolArea {
     pub fn step_patrol(&mut self) -> bool {
         self.guard.patrol(&self.obstacles, self.width, self.height)
     }
+
+    /// Whether adding `obstruction` would trap the [Guard] in a loop from their starting position
+    fn traps_guard(&self, obstruction: Obstacle) -> bool {
+        let mut obstacles = self.obstacles.clone();
+        obstacles.push(obstruction);
+
+        let mut guard = Guard::new(self.guard.current_position, self.guard.direction);
+        guard.patrol_loops(&obstacles, self.width, self.height)
+    }
 }
 
 impl FromStr for PatrolArea {
-    type Err = String;
+    type Err = Diagnostic;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut guard_dir: Option<Direction> = None;
-        let mut guard_pos: Option<Position> = None;
-        let mut width = 0;
-        let mut height = 0;
-        let mut obstacles = Vec::new();
-
-        for (y, line) in s.trim().lines().enumerate() {
-            height += 1;
-            if width == 0 {
-                width = line.len();
-            }
-
-            for (x, c) in line.chars().enumerate() {
-                if guard_pos.is_none() && guard_dir.is_none() {
-                    if let Ok(direction) = Direction::try_from(c) {
-                        guard_dir = Some(direction);
-                        guard_pos = Some((x, y));
-                    }
-                }
-
-                if c == '#' {
-                    obstacles.push(Obstacle::new(x, y));
-                }
-            }
-        }
-
-        if guard_dir.is_none() || guard_pos.is_none() {
-            return Err("Could not locate guard".to_string());
-        }
-
-        let guard = Guard::new(guard_pos.unwrap(), guard_dir.unwrap());
+        let cell = |c: char| (matches!(c, '.' | '#') || guard_direction(c).is_some()).then_some(c);
+        let map: Grid<char> = parse_all(grid(cell), s)?;
+
+        let (guard_pos, guard_dir) = map
+            .iter()
+            .find_map(|(pos, &c)| Some((pos, guard_direction(c)?)))
+            .ok_or_else(|| Diagnostic::at(s, s.len(), "a guard drawn as '^', '>', 'v' or '<'"))?;
+        let obstacles = map
+            .iter()
+            .filter(|&(_, &c)| c == '#')
+            .map(|((x, y), _)| Obstacle::new(x, y))
+            .collect();
 
         Ok(Self {
-            guard,
+            guard: Guard::new(guard_pos, guard_dir),
             obstacles,
-            width,
-            height,
+            width: map.width(),
+            height: map.height(),
         })
     }
 }
@@ -283,10 +270,10 @@ impl Display for PatrolArea {
                     CellContents::Empty => '░',
                     CellContents::Obstacle => '█',
                     CellContents::Guard => match self.guard.direction {
-                        Direction::North => '▲',
-                        Direction::East => '▶',
-                        Direction::South => '▼',
-                        Direction::West => '◀',
+                        Direction4::North => '▲',
+                        Direction4::East => '▶',
+                        Direction4::South => '▼',
+                        Direction4::West => '◀',
                     },
                     CellContents::Visited => '◈',
                 };
@@ -299,63 +286,67 @@ impl Display for PatrolArea {
     }
 }
 
-#[derive(Default)]
-pub struct Day06 {
-    patrol_area: PatrolArea,
-}
-
-impl Display for Day06 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let patrol_area = format!("{}", self.patrol_area);
-        let guard_info = format!(
-            "Guard at ({}, {})",
-            self.patrol_area.guard.current_position.0, self.patrol_area.guard.current_position.1
-        );
+pub struct Day06;
 
-        write!(f, "{}{}", patrol_area, guard_info)
+impl Day06 {
+    /// Patrols a copy of the [PatrolArea] until the [Guard] leaves it
+    fn distinct_patrol_position_count(patrol_area: &PatrolArea) -> usize {
+        let mut patrol_area = patrol_area.clone();
+        while patrol_area.step_patrol() {}
+        patrol_area.guard.distinct_positions.len() - 1
     }
-}
 
-impl Day06 {
-    fn distinct_patrol_position_count(&mut self) -> usize {
-        while self.patrol_area.step_patrol() {}
-        self.patrol_area.guard.distinct_positions.len() - 1
+    /// Counts the cells where a single new [Obstacle] would trap the [Guard] in a loop
+    ///
+    /// Only cells on the [Guard]'s original route can change where they go, and the starting
+    /// position is off-limits.
+    fn loop_obstruction_count(patrol_area: &PatrolArea) -> usize {
+        let start = patrol_area.guard.current_position;
+        let mut patrolled = patrol_area.clone();
+        while patrolled.step_patrol() {}
+
+        patrolled
+            .guard
+            .distinct_positions
+            .iter()
+            .filter(|&&(x, y)| x < patrol_area.width && y < patrol_area.height)
+            .filter(|&&position| position != start)
+            .filter(|&&(x, y)| patrol_area.traps_guard(Obstacle::new(x, y)))
+            .count()
     }
 }
 
-const EXAMPLE_INPUT: &str = include_str!("../example_input.txt");
-
-impl AoCDay for Day06 {
-    fn part1(&mut self) {
-        // while self.patrol_area.step_patrol() {
-        //     println!("{}", self);
-        //     thread::sleep(Duration::from_millis(125));
-        // }
-        // println!("{}", self);
-        // let count = self.patrol_area.guard.distinct_positions.len();
-        let count = self.distinct_patrol_position_count();
-        println!("\nDistinct patrol positions: {}", count);
-    }
+impl Solution for Day06 {
+    type Input = PatrolArea;
 
-    fn part2(&mut self) {
-        todo!()
+    fn parse(input: &str) -> AoCResult<PatrolArea> {
+        Ok(PatrolArea::from_str(input)?)
     }
 
-    fn load_input(&mut self, path: &Path) -> anyhow::Result<()> {
-        let input = std::fs::read_to_string(path)?;
-        let patrol_area = PatrolArea::from_str(&input).unwrap();
+    fn part1(patrol_area: &PatrolArea) -> AoCResult<Answer> {
+        let count = Self::distinct_patrol_position_count(patrol_area);
+        Ok(Answer::new("Distinct patrol positions", count))
+    }
 
-        self.patrol_area = patrol_area;
-        Ok(())
+    fn part2(patrol_area: &PatrolArea) -> AoCResult<Answer> {
+        let count = Self::loop_obstruction_count(patrol_area);
+        Ok(Answer::new("Loop obstruction positions", count))
     }
 }
 
+aoc_day::register_day!(6, "Guard Gallivant", SolutionDay<Day06>, examples: [
+    Example::new("example", include_str!("../example_input.txt")).part1("41").part2("6"),
+]);
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_day::AoCDay;
     use std::path::PathBuf;
     use std::sync::LazyLock;
 
+    const EXAMPLE_INPUT: &str = include_str!("../example_input.txt");
+
     static EXAMPLE_PATH: LazyLock<PathBuf> =
         LazyLock::new(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("example_input.txt"));
 
@@ -366,14 +357,60 @@ mod tests {
         assert!(patrol_area.is_ok());
     }
 
+    #[test]
+    fn parse_errors_are_located() {
+        let Err(err) = PatrolArea::from_str("..#\n.x^\n") else {
+            panic!("parsed a patrol area containing 'x'");
+        };
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(err.expected, "a grid cell");
+
+        let Err(err) = PatrolArea::from_str("..#\n...\n") else {
+            panic!("parsed a patrol area without a guard");
+        };
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 1: expected a guard drawn as '^', '>', 'v' or '<', found end of input"
+        );
+    }
+
     #[test]
     fn part1() {
-        let mut day = Day06::default();
-        day.load_input(&EXAMPLE_PATH).unwrap();
+        let patrol_area = Day06::parse(EXAMPLE_INPUT).unwrap();
 
         let expected = 41;
-        let actual = day.distinct_patrol_position_count();
+        let actual = Day06::distinct_patrol_position_count(&patrol_area);
 
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn obstruction_traps_guard() {
+        let patrol_area = Day06::parse(EXAMPLE_INPUT).unwrap();
+
+        assert!(patrol_area.traps_guard(Obstacle::new(3, 6)));
+        assert!(!patrol_area.traps_guard(Obstacle::new(1, 1)));
+    }
+
+    #[test]
+    fn part2() {
+        let patrol_area = Day06::parse(EXAMPLE_INPUT).unwrap();
+
+        let expected = 6;
+        let actual = Day06::loop_obstruction_count(&patrol_area);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn part1_is_repeatable() {
+        let mut day = SolutionDay::<Day06>::default();
+        day.load_input(&EXAMPLE_PATH).unwrap();
+
+        let first = day.part1().unwrap();
+        let second = day.part1().unwrap();
+
+        assert_eq!(first.as_integer(), Some(41));
+        assert_eq!(first, second);
+    }
 }
@@ -1,10 +1,13 @@
-use std::fmt::{write, Formatter};
+use aoc_day::{Answer, AoCResult, Diagnostic, Example, Solution, SolutionDay};
+use std::fmt::Formatter;
 use std::str::FromStr;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 enum Operator {
     Add,
     Mul,
+    /// Joins the digits of both sides, so `12 || 345` is `12345`
+    Concat,
 }
 
 impl std::fmt::Display for Operator {
@@ -14,36 +17,62 @@ impl std::fmt::Display for Operator {
 }
 
 impl Operator {
-    const COUNT: u64 = 2;
+    /// The operators available in part 1
+    const ARITHMETIC: [Operator; 2] = [Self::Add, Self::Mul];
+    /// Every operator, as available in part 2
+    const ALL: [Operator; 3] = [Self::Add, Self::Mul, Self::Concat];
 
-    fn next(&self) -> Self {
+    /// Applies the operator, returning `None` if the result does not fit in a `u64`
+    #[cfg(test)]
+    fn apply(&self, lhs: u64, rhs: u64) -> Option<u64> {
         match self {
-            Self::Add => Self::Mul,
-            Self::Mul => Self::Add,
+            Self::Add => lhs.checked_add(rhs),
+            Self::Mul => lhs.checked_mul(rhs),
+            Self::Concat => {
+                let rhs_digits = rhs.checked_ilog10().unwrap_or(0) + 1;
+                lhs.checked_mul(10u64.checked_pow(rhs_digits)?)?
+                    .checked_add(rhs)
+            }
         }
     }
 
-    fn prev(&self) -> Self {
+    /// Finds the left-hand side which, combined with `rhs`, gives `result`
+    ///
+    /// Returns `None` if there is no such value, such as when `rhs` does not divide `result` or
+    /// `result` does not end with the digits of `rhs`. Multiplying by zero is also `None`: there is
+    /// no single left-hand side to return when `result` is zero, as every one would do, so callers
+    /// must check for that case themselves.
+    fn undo(&self, result: u64, rhs: u64) -> Option<u64> {
         match self {
-            Self::Add => Self::Mul,
-            Self::Mul => Self::Add,
+            Self::Add => result.checked_sub(rhs),
+            Self::Mul => (rhs != 0 && result.is_multiple_of(rhs)).then(|| result / rhs),
+            Self::Concat => {
+                let rhs_digits = rhs.checked_ilog10().unwrap_or(0) + 1;
+                let shift = 10u64.checked_pow(rhs_digits)?;
+                (result % shift == rhs).then(|| result / shift)
+            }
         }
     }
 }
 
-struct OpPermutator {
+/// Iterates over every way of filling a fixed number of slots from a set of [Operator]s
+///
+/// Only used to cross-check [Equation::is_possible] against every combination of operators.
+#[cfg(test)]
+struct OpPermutator<'a> {
+    /// The operators which may fill each slot
+    operator_set: &'a [Operator],
     operators: Vec<Operator>,
+    /// Index into `operator_set` of the operator in each slot
+    choices: Vec<usize>,
     /// Maximum number of mutations
     permutation_max: u64,
     /// Number of times a mutation has occurred.
     permutation_count: u64,
-    /// Current index being held in-place
-    hold_idx: usize,
-    /// Current index to increment
-    inc_idx: usize,
 }
 
-impl std::fmt::Display for OpPermutator {
+#[cfg(test)]
+impl std::fmt::Display for OpPermutator<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let ops_str: String = self.operators.iter().fold(String::new(), |acc, op| {
             if acc.is_empty() {
@@ -57,48 +86,39 @@ impl std::fmt::Display for OpPermutator {
     }
 }
 
-/*
-   000
-   010
-   001
-   100
-   110
-   101
-   110
-   111
-*/
-
-impl OpPermutator {
-    fn new(size: usize) -> Self {
+#[cfg(test)]
+impl<'a> OpPermutator<'a> {
+    fn new(size: usize, operator_set: &'a [Operator]) -> Self {
+        assert!(!operator_set.is_empty(), "no operators to choose from");
+
         Self {
-            operators: vec![Operator::Add; size],
-            permutation_max: Operator::COUNT.pow(size as u32),
+            operator_set,
+            operators: vec![operator_set[0]; size],
+            choices: vec![0; size],
+            permutation_max: (operator_set.len() as u64).pow(size as u32),
             permutation_count: 0,
-            hold_idx: 0,
-            inc_idx: 1,
         }
     }
 
-    fn update_operator_next(&mut self, idx: usize) {
-        let updated_operator = self.operators[idx].next();
-        self.operators[idx] = updated_operator;
-    }
-
-    fn update_operator_prev(&mut self, idx: usize) {
-        let updated_operator = self.operators[idx].prev();
-        self.operators[idx] = updated_operator;
+    /// Moves the slot at `idx` on to the next operator in the set, wrapping back to the first
+    ///
+    /// # Return
+    ///
+    /// `true` if the slot wrapped around
+    fn update_operator_next(&mut self, idx: usize) -> bool {
+        let choice = (self.choices[idx] + 1) % self.operator_set.len();
+        self.choices[idx] = choice;
+        self.operators[idx] = self.operator_set[choice];
+        choice == 0
     }
 
     fn fully_mutated(&self) -> bool {
         self.permutation_count >= self.permutation_max
     }
-
-    fn get_ops(&self) -> Vec<Operator> {
-        self.operators.clone()
-    }
 }
 
-impl Iterator for OpPermutator {
+#[cfg(test)]
+impl Iterator for OpPermutator<'_> {
     type Item = Vec<Operator>;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -110,28 +130,12 @@ impl Iterator for OpPermutator {
             return Some(self.operators.clone());
         }
 
-        let ops_len = self.operators.len();
-
-        if self.hold_idx < ops_len - 1 {
-            if self.inc_idx >= ops_len {
-                // Decrement the final value and move on to the next set
-                self.update_operator_prev(ops_len - 1);
-                self.update_operator_prev(self.hold_idx);
-                self.hold_idx += 1;
-                self.inc_idx = self.hold_idx + 1;
-            } else {
-                if self.inc_idx > self.hold_idx + 1 {
-                    // Increment the value at the current 'inc_idx' and decrement the one behind it
-                    self.update_operator_prev(self.inc_idx - 1);
-                }
-                self.update_operator_next(self.inc_idx);
-                self.inc_idx += 1;
+        // Count through the permutations like an odometer: advance the first operator, carrying
+        // into the next one whenever an operator wraps back around to the first
+        for idx in 0..self.operators.len() {
+            if !self.update_operator_next(idx) {
+                break;
             }
-        } else if self.hold_idx == ops_len - 1 {
-            self.update_operator_next(self.hold_idx);
-        } else {
-            dbg!(&self.operators);
-            return None;
         }
 
         self.permutation_count += 1;
@@ -140,100 +144,270 @@ impl Iterator for OpPermutator {
 }
 
 #[derive(Clone, Eq, PartialEq, Debug)]
-struct Equation {
+pub struct Equation {
     expected_result: u64,
     inputs: Vec<u64>,
 }
 
 impl FromStr for Equation {
-    type Err = ();
+    type Err = Diagnostic;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (_, eq) = parse::equation(s).unwrap();
-
-        Ok(eq)
+        aoc_parse::parse_all(parse::equation, s)
     }
 }
 
 impl Equation {
-    fn is_possible(&self) -> bool {
-        let ops = OpPermutator::new(self.inputs.len() - 1);
-
-        let mut result;
-        for permutation in ops {
-            result = self.inputs[0];
-            for (i, input) in self.inputs.iter().skip(1).enumerate() {
-                match permutation[i] {
-                    Operator::Add => {
-                        result += *input;
-                    }
-                    Operator::Mul => {
-                        result *= input;
-                    }
-                }
-            }
+    /// Whether some choice of `operators`, applied left to right, produces the expected result
+    fn is_possible(&self, operators: &[Operator]) -> bool {
+        Self::can_produce(self.expected_result, &self.inputs, operators)
+    }
 
-            if result == self.expected_result {
-                return true;
-            }
+    /// Works backward from `target`, undoing the operator before the last input
+    ///
+    /// Each operator can only be undone when it could have produced `target`, so most branches
+    /// are pruned after a step or two rather than being tried all the way through. Multiplying
+    /// by a zero last input produces a zero target from whatever the earlier inputs make.
+    fn can_produce(target: u64, inputs: &[u64], operators: &[Operator]) -> bool {
+        match inputs {
+            [] => false,
+            [first] => *first == target,
+            [rest @ .., last] => operators.iter().any(|op| {
+                (*op == Operator::Mul && *last == 0 && target == 0)
+                    || op
+                        .undo(target, *last)
+                        .is_some_and(|lhs| Self::can_produce(lhs, rest, operators))
+            }),
         }
+    }
 
-        false
+    /// Reference implementation of [Self::is_possible] which tries every combination of
+    /// `operators` in turn
+    #[cfg(test)]
+    fn is_possible_by_enumeration(&self, operators: &[Operator]) -> bool {
+        let mut ops = OpPermutator::new(self.inputs.len() - 1, operators);
+
+        ops.any(|permutation| {
+            let result = self.inputs[1..]
+                .iter()
+                .zip(&permutation)
+                .try_fold(self.inputs[0], |result, (input, op)| {
+                    op.apply(result, *input)
+                });
+
+            result == Some(self.expected_result)
+        })
     }
 }
 
+pub struct Day07;
+
+impl Day07 {
+    /// Sums the expected results of every [Equation] which some choice of `operators` can satisfy
+    fn calibration_result(equations: &[Equation], operators: &[Operator]) -> u64 {
+        equations
+            .iter()
+            .filter(|equation| equation.is_possible(operators))
+            .map(|equation| equation.expected_result)
+            .sum()
+    }
+}
+
+impl Solution for Day07 {
+    type Input = Vec<Equation>;
+
+    fn parse(input: &str) -> AoCResult<Vec<Equation>> {
+        Ok(parse::all_equations(input)?)
+    }
+
+    fn part1(equations: &Vec<Equation>) -> AoCResult<Answer> {
+        let total = Self::calibration_result(equations, &Operator::ARITHMETIC);
+        Ok(Answer::new("Total calibration result", total))
+    }
+
+    fn part2(equations: &Vec<Equation>) -> AoCResult<Answer> {
+        let total = Self::calibration_result(equations, &Operator::ALL);
+        Ok(Answer::new(
+            "Total calibration result with concatenation",
+            total,
+        ))
+    }
+}
+
+aoc_day::register_day!(7, "Bridge Repair", SolutionDay<Day07>, examples: [
+    Example::new("example", include_str!("../example.txt"))
+        .part1("3749")
+        .part2("11387"),
+]);
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::collections::HashSet;
 
+    const TEST_INPUT: &str = include_str!("../example.txt");
+
     #[test]
     fn mutator_test() {
-        let ops = OpPermutator::new(3);
+        let ops = OpPermutator::new(3, &Operator::ARITHMETIC);
 
         assert_eq!(ops.permutation_max, 8);
-        let mut mutation_set = HashSet::with_capacity(ops.permutation_max as usize);
-        mutation_set.insert(ops.get_ops());
-
-        let print_ops = |ops: &[Operator]| {
-            let ops_str = ops.iter().fold(String::new(), |acc, op| {
-                if acc.is_empty() {
-                    format!("{op}")
-                } else {
-                    format!("{acc}, {op}")
-                }
-            });
-            println!("[{ops_str}]");
+        let mutation_set: HashSet<Vec<Operator>> = ops.collect();
+
+        assert_eq!(mutation_set.len(), 8)
+    }
+
+    #[test]
+    fn mutator_any_operator_count() {
+        let ops = OpPermutator::new(3, &Operator::ALL);
+        assert_eq!(ops.permutation_max, 27);
+
+        let permutations: Vec<Vec<Operator>> = ops.collect();
+        let mutation_set: HashSet<&Vec<Operator>> = permutations.iter().collect();
+        assert_eq!(permutations.len(), 27);
+        assert_eq!(mutation_set.len(), 27);
+        assert_eq!(permutations.last(), Some(&vec![Operator::Concat; 3]));
+    }
+
+    #[test]
+    fn concatenation() {
+        assert_eq!(Operator::Concat.apply(12, 345), Some(12345));
+        assert_eq!(Operator::Concat.apply(15, 6), Some(156));
+        assert_eq!(Operator::Concat.apply(7, 0), Some(70));
+        assert_eq!(Operator::Concat.apply(u64::MAX / 10, 99), None);
+
+        let equation = Equation::from_str("7290: 6 8 6 15").unwrap();
+        assert!(!equation.is_possible(&Operator::ARITHMETIC));
+        assert!(equation.is_possible(&Operator::ALL));
+    }
+
+    #[test]
+    fn single_input_needs_no_operators() {
+        let mut ops = OpPermutator::new(0, &Operator::ALL);
+
+        assert_eq!(ops.next(), Some(vec![]));
+        assert_eq!(ops.next(), None);
+        assert!(Equation::from_str("5: 5")
+            .unwrap()
+            .is_possible(&Operator::ARITHMETIC));
+    }
+
+    #[test]
+    fn possible_equations() {
+        let equations = parse::all_equations(TEST_INPUT).unwrap();
+        let possible = |operators: &[Operator]| -> Vec<u64> {
+            equations
+                .iter()
+                .filter(|equation| equation.is_possible(operators))
+                .map(|equation| equation.expected_result)
+                .collect()
+        };
+
+        assert_eq!(possible(&Operator::ARITHMETIC), [190, 3267, 292]);
+        assert_eq!(possible(&Operator::ALL), [190, 3267, 156, 7290, 192, 292]);
+    }
+
+    #[test]
+    fn multiplying_by_zero() {
+        let possible = |equation: &str| {
+            Equation::from_str(equation)
+                .unwrap()
+                .is_possible(&Operator::ARITHMETIC)
         };
 
-        for permutation in ops {
-            print_ops(&permutation);
-            mutation_set.insert(permutation);
+        assert!(possible("3: 5 0 3"));
+        assert!(possible("0: 5 0"));
+        assert!(possible("0: 7 6 0 0"));
+        assert!(!possible("6: 5 0"));
+    }
+
+    #[test]
+    fn undo_operators() {
+        assert_eq!(Operator::Add.undo(190, 19), Some(171));
+        assert_eq!(Operator::Add.undo(10, 19), None);
+        assert_eq!(Operator::Mul.undo(190, 19), Some(10));
+        assert_eq!(Operator::Mul.undo(191, 19), None);
+        assert_eq!(Operator::Mul.undo(0, 0), None);
+        assert_eq!(Operator::Concat.undo(12345, 345), Some(12));
+        assert_eq!(Operator::Concat.undo(12345, 45), Some(123));
+        assert_eq!(Operator::Concat.undo(12345, 346), None);
+        assert_eq!(Operator::Concat.undo(70, 0), Some(7));
+    }
+
+    /// Compares [Equation::is_possible] to trying every combination of operators
+    fn assert_matches_enumeration(equation: &Equation) {
+        for operators in [&Operator::ARITHMETIC[..], &Operator::ALL[..]] {
+            assert_eq!(
+                equation.is_possible(operators),
+                equation.is_possible_by_enumeration(operators),
+                "{equation:?} with {operators:?}"
+            );
         }
+    }
 
-        // for val in &mutation_set {
-        //
-        // }
+    #[test]
+    fn backward_search_matches_enumeration_example() {
+        for equation in parse::all_equations(TEST_INPUT).unwrap() {
+            assert_matches_enumeration(&equation);
+        }
+    }
 
-        assert_eq!(mutation_set.len(), 8)
+    #[test]
+    fn backward_search_matches_enumeration_generated() {
+        // A small linear congruential generator keeps the generated equations the same every run
+        let mut seed: u64 = 0x2024_0007;
+        let mut random = |bound: u64| {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 33) % bound
+        };
+
+        for _ in 0..500 {
+            let inputs: Vec<u64> = (0..=random(6)).map(|_| random(30)).collect();
+            let result = inputs[1..].iter().fold(inputs[0], |result, input| {
+                let op = Operator::ALL[random(3) as usize];
+                op.apply(result, *input).unwrap()
+            });
+
+            for expected_result in [result, result + 1, result.saturating_sub(1)] {
+                assert_matches_enumeration(&Equation {
+                    expected_result,
+                    inputs: inputs.clone(),
+                });
+            }
+        }
+    }
+
+    #[test]
+    fn part1_test() {
+        let equations = Day07::parse(TEST_INPUT).unwrap();
+
+        assert_eq!(
+            Day07::calibration_result(&equations, &Operator::ARITHMETIC),
+            3749
+        );
+    }
+
+    #[test]
+    fn part2_test() {
+        let equations = Day07::parse(TEST_INPUT).unwrap();
+
+        assert_eq!(Day07::calibration_result(&equations, &Operator::ALL), 11387);
     }
 }
 
 mod parse {
     use super::Equation;
-    use nom::branch::alt;
-    use nom::bytes::complete::tag;
-    use nom::character::complete;
-    use nom::combinator::eof;
-    use nom::multi::{separated_list0, separated_list1};
-    use nom::sequence::{terminated, tuple};
-    use nom::IResult;
-
-    pub fn equation(input: &str) -> IResult<&str, Equation> {
-        let get_expected_result = terminated(complete::u64, tag(": "));
-        let get_inputs = separated_list1(complete::multispace1, complete::u64);
+    use aoc_day::Diagnostic;
+    use aoc_parse::{key_value, lines_of, parse_all, spaced, unsigned, PResult};
+    use nom::error::context;
 
-        let (input, (expected_result, inputs)) = tuple((get_expected_result, get_inputs))(input)?;
+    pub fn equation(input: &str) -> PResult<'_, Equation> {
+        let (input, (expected_result, inputs)) = context(
+            "an equation such as 190: 10 19",
+            key_value(unsigned, spaced(unsigned)),
+        )(input)?;
 
         let equation = Equation {
             expected_result,
@@ -243,9 +417,8 @@ mod parse {
         Ok((input, equation))
     }
 
-    pub fn all_equations(input: &str) -> IResult<&str, Vec<Equation>> {
-        let ending_options = alt((complete::line_ending, eof));
-        separated_list0(ending_options, equation)(input)
+    pub fn all_equations(input: &str) -> Result<Vec<Equation>, Diagnostic> {
+        parse_all(lines_of(equation), input)
     }
 
     #[cfg(test)]
@@ -270,20 +443,28 @@ mod parse {
 
         #[test]
         fn parse_all_equations() {
-            let expected = vec![
-                Equation {
-                    expected_result: 7290,
-                    inputs: vec![6, 8, 6, 15],
-                },
-                Equation {
-                    expected_result: 190,
-                    inputs: vec![10, 19],
-                },
-            ];
-            let (remainder, actual) = all_equations(TEST_INPUT).unwrap();
+            let expected_first = Equation {
+                expected_result: 190,
+                inputs: vec![10, 19],
+            };
+            let expected_last = Equation {
+                expected_result: 292,
+                inputs: vec![11, 6, 16, 20],
+            };
+            let actual = all_equations(TEST_INPUT).unwrap();
 
-            assert!(remainder.is_empty());
-            assert_eq!(expected, actual);
+            assert_eq!(actual.len(), 9);
+            assert_eq!(actual.first(), Some(&expected_first));
+            assert_eq!(actual.last(), Some(&expected_last));
+        }
+
+        #[test]
+        fn malformed_equation_is_an_error() {
+            let Err(err) = all_equations("190: 10 19\n3267 81 40 27\n") else {
+                panic!("malformed equation was accepted");
+            };
+
+            assert_eq!((err.line, err.column), (2, 5));
         }
     }
 }
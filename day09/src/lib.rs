@@ -1,10 +1,7 @@
-use aoc_day::AoCDay;
+use aoc_day::{Answer, AoCResult, Diagnostic, Example, Solution, SolutionDay};
 use std::collections::VecDeque;
 use std::fmt::Display;
-use std::io::Write;
-use std::path::PathBuf;
 use std::str::FromStr;
-use std::sync::LazyLock;
 
 #[derive(Default, Debug, Eq, PartialEq, Copy, Clone)]
 enum Block {
@@ -15,8 +12,8 @@ enum Block {
     Free,
 }
 
-#[derive(Debug, Default)]
-struct FileSystem {
+#[derive(Debug, Default, Clone)]
+pub struct FileSystem {
     blocks: VecDeque<Block>,
     last_file_block: usize,
     free_blocks: VecDeque<usize>,
@@ -34,7 +31,7 @@ impl Display for FileSystem {
 }
 
 impl FromStr for FileSystem {
-    type Err = ();
+    type Err = Diagnostic;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut file_id = 0;
@@ -43,7 +40,10 @@ impl FromStr for FileSystem {
         let mut is_file = true;
         let mut last_file_block = 0;
 
-        s.chars().filter_map(|c| c.to_digit(10)).for_each(|val| {
+        for (offset, c) in s.trim_end().char_indices() {
+            let val = c
+                .to_digit(10)
+                .ok_or_else(|| Diagnostic::at(s, offset, "a block count from 0 to 9"))?;
             let end_idx = current_idx + val as usize;
             let blocks_range = current_idx..end_idx;
             current_idx = end_idx;
@@ -57,14 +57,9 @@ impl FromStr for FileSystem {
                 blocks.extend(blocks_range.map(|_| Block::Free));
                 is_file = true;
             }
-        });
+        }
 
-        let free_blocks = blocks
-            .iter()
-            .enumerate()
-            .filter(|(_, b)| **b == Block::Free)
-            .map(|(i, _)| i)
-            .collect();
+        let free_blocks = FileSystem::free_block_indices(&blocks);
 
         Ok(FileSystem {
             blocks,
@@ -75,6 +70,67 @@ impl FromStr for FileSystem {
 }
 
 impl FileSystem {
+    fn free_block_indices(blocks: &VecDeque<Block>) -> VecDeque<usize> {
+        blocks
+            .iter()
+            .enumerate()
+            .filter(|(_, b)| **b == Block::Free)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Splits the blocks into runs of the same [Block], as `(start index, length, block)`
+    fn spans(&self) -> Vec<(usize, usize, Block)> {
+        let mut spans: Vec<(usize, usize, Block)> = Vec::new();
+        for (i, block) in self.blocks.iter().enumerate() {
+            match spans.last_mut() {
+                Some((_, len, last)) if last == block => *len += 1,
+                _ => spans.push((i, 1, *block)),
+            }
+        }
+        spans
+    }
+
+    /// Moves whole files, in descending file ID order, into the leftmost free span large enough
+    /// to hold them
+    ///
+    /// Files are only ever moved to the left, and each file is attempted exactly once.
+    fn compact_files(&mut self) {
+        let mut files = Vec::new();
+        let mut free_spans = Vec::new();
+        for (start, len, block) in self.spans() {
+            match block {
+                Block::File(id) => files.push((id, start, len)),
+                Block::Free => free_spans.push((start, len)),
+            }
+        }
+        files.sort_unstable_by_key(|&(id, _, _)| std::cmp::Reverse(id));
+
+        for (id, file_start, file_len) in files {
+            let Some(span) = free_spans
+                .iter_mut()
+                .take_while(|(free_start, _)| *free_start < file_start)
+                .find(|(_, free_len)| *free_len >= file_len)
+            else {
+                continue;
+            };
+
+            for offset in 0..file_len {
+                self.blocks[span.0 + offset] = Block::File(id);
+                self.blocks[file_start + offset] = Block::Free;
+            }
+            span.0 += file_len;
+            span.1 -= file_len;
+        }
+
+        self.free_blocks = Self::free_block_indices(&self.blocks);
+        self.last_file_block = self
+            .blocks
+            .iter()
+            .rposition(|b| *b != Block::Free)
+            .unwrap_or_default();
+    }
+
     fn fragment_last_file_block(&mut self) {
         if let Some(first_free_block_idx) = self.free_blocks.pop_front() {
             self.blocks[first_free_block_idx] = self.blocks[self.last_file_block];
@@ -115,43 +171,34 @@ impl FileSystem {
     }
 }
 
-#[derive(Default)]
-pub struct Day09 {
-    fs: FileSystem,
-}
+pub struct Day09;
 
-impl AoCDay for Day09 {
-    fn part1(&mut self) {
-        self.fs.fragment();
-        self.save_output().unwrap();
-        let checksum = self.fs.checksum();
-        println!("FS checksum: {}", checksum);
-    }
+impl Solution for Day09 {
+    type Input = FileSystem;
 
-    fn part2(&mut self) {
-        todo!()
+    fn parse(input: &str) -> AoCResult<FileSystem> {
+        Ok(FileSystem::from_str(input)?)
     }
 
-    fn load_input(&mut self, input_path: &std::path::Path) -> anyhow::Result<()> {
-        let input = std::fs::read_to_string(input_path)?;
-        self.fs = FileSystem::from_str(&input).unwrap();
-        Ok(())
+    fn part1(fs: &FileSystem) -> AoCResult<Answer> {
+        let mut fs = fs.clone();
+        fs.fragment();
+        let checksum = fs.checksum();
+        Ok(Answer::new("FS checksum", checksum))
     }
-}
-
-static OUTPUT_PATH: LazyLock<PathBuf> =
-    LazyLock::new(|| PathBuf::from("outputs").join("day09.txt"));
 
-impl Day09 {
-    pub fn save_output(&self) -> anyhow::Result<()> {
-        println!("Saving output to {}", OUTPUT_PATH.as_path().display());
-        let file = std::fs::File::create(OUTPUT_PATH.as_path())?;
-        let mut writer = std::io::BufWriter::new(file);
-        write!(writer, "{}", self.fs)?;
-        Ok(())
+    fn part2(fs: &FileSystem) -> AoCResult<Answer> {
+        let mut fs = fs.clone();
+        fs.compact_files();
+        let checksum = fs.checksum();
+        Ok(Answer::new("FS checksum", checksum))
     }
 }
 
+aoc_day::register_day!(9, "Disk Fragmenter", SolutionDay<Day09>, examples: [
+    Example::new("example", include_str!("../example_input.txt")).part1("1928").part2("2858"),
+]);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -164,6 +211,22 @@ mod tests {
         assert_eq!(fs.to_string(), "00...111...2...333.44.5555.6666.777.888899");
     }
 
+    #[test]
+    fn test_solution_parse() {
+        let fs = Day09::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(fs.to_string(), "00...111...2...333.44.5555.6666.777.888899");
+    }
+
+    #[test]
+    fn test_parse_rejects_non_digits() {
+        let err = FileSystem::from_str("2333x1\n").unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 5: expected a block count from 0 to 9, found 'x1'"
+        );
+    }
+
     #[test]
     fn test_defrag_last_file_block() {
         let mut fs = FileSystem::from_str(EXAMPLE_INPUT).unwrap();
@@ -180,4 +243,22 @@ mod tests {
         assert_eq!(fs.to_string(), "0099811188827773336446555566..............");
         assert_eq!(fs.checksum(), 1928);
     }
+
+    #[test]
+    fn test_compact_files_example_input() {
+        let mut fs = FileSystem::from_str(EXAMPLE_INPUT).unwrap();
+        fs.compact_files();
+
+        assert_eq!(fs.to_string(), "00992111777.44.333....5555.6666.....8888..");
+        assert_eq!(fs.checksum(), 2858);
+    }
+
+    #[test]
+    fn test_compact_files_leaves_unmovable_files() {
+        let mut fs = FileSystem::from_str("12345").unwrap();
+        fs.compact_files();
+
+        assert_eq!(fs.to_string(), "0..111....22222");
+        assert_eq!(fs.checksum(), 132);
+    }
 }
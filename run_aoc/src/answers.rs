@@ -0,0 +1,183 @@
+use crate::{Day, Part};
+use anyhow::{anyhow, bail, Context};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Name under which answers for a day's real puzzle input are recorded
+pub const PUZZLE_INPUT: &str = "input";
+
+/// A known-correct answer for one part of a day, run against a named input
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ExpectedAnswer {
+    pub day: Day,
+    pub part: Part,
+    /// Which input the answer belongs to: [PUZZLE_INPUT] or the name of one of the day's examples
+    pub input: String,
+    /// The answer as it is displayed, without its label
+    pub value: String,
+}
+
+/// Expected answers loaded from a TOML file
+///
+/// Each table is named `dayNN.<input>` and holds `part1`/`part2` keys. Values may be integers or
+/// strings:
+///
+/// ```toml
+/// [day01.input]
+/// part1 = 1234
+/// part2 = 5678
+/// ```
+#[derive(Debug, Default)]
+pub struct AnswerStore {
+    answers: Vec<ExpectedAnswer>,
+}
+
+impl AnswerStore {
+    /// Default location of the checked-in answers file
+    pub fn default_path() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("answers.toml")
+    }
+
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Could not read answers from {}", path.display()))?;
+
+        text.parse()
+            .with_context(|| format!("Could not parse answers in {}", path.display()))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ExpectedAnswer> {
+        self.answers.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.answers.is_empty()
+    }
+}
+
+fn parse_day_key(key: &str) -> anyhow::Result<Day> {
+    key.strip_prefix("day")
+        .and_then(|num| num.parse::<u8>().ok())
+        .and_then(|num| Day::try_from(num).ok())
+        .ok_or_else(|| anyhow!("Invalid day key: {key}"))
+}
+
+fn parse_part_key(key: &str) -> anyhow::Result<Part> {
+    match key {
+        "part1" => Ok(Part::One),
+        "part2" => Ok(Part::Two),
+        _ => bail!("Invalid part key: {key}"),
+    }
+}
+
+impl FromStr for AnswerStore {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let table: toml::Table = s.parse()?;
+        let mut answers = Vec::new();
+
+        for (day_key, inputs) in &table {
+            let day = parse_day_key(day_key)?;
+            let inputs = inputs
+                .as_table()
+                .ok_or_else(|| anyhow!("{day_key} must be a table of inputs"))?;
+
+            for (input, parts) in inputs {
+                let parts = parts
+                    .as_table()
+                    .ok_or_else(|| anyhow!("{day_key}.{input} must be a table of parts"))?;
+
+                for (part_key, value) in parts {
+                    let part = parse_part_key(part_key)?;
+                    let value = match value {
+                        toml::Value::Integer(val) => val.to_string(),
+                        toml::Value::String(val) => val.clone(),
+                        _ => bail!("{day_key}.{input}.{part_key} must be an integer or string"),
+                    };
+
+                    answers.push(ExpectedAnswer {
+                        day,
+                        part,
+                        input: input.clone(),
+                        value,
+                    });
+                }
+            }
+        }
+
+        answers.sort_by(|a, b| {
+            (a.day, u8::from(a.part), &a.input).cmp(&(b.day, u8::from(b.part), &b.input))
+        });
+
+        Ok(Self { answers })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_answers() {
+        let store: AnswerStore = r#"
+            [day02.input]
+            part2 = 4
+            part1 = "2"
+
+            [day01.input]
+            part1 = 11
+        "#
+        .parse()
+        .unwrap();
+
+        let answers: Vec<_> = store.iter().cloned().collect();
+        assert_eq!(answers.len(), 3);
+        assert_eq!(
+            answers[0],
+            ExpectedAnswer {
+                day: Day(1),
+                part: Part::One,
+                input: PUZZLE_INPUT.to_string(),
+                value: "11".to_string(),
+            }
+        );
+        assert_eq!(answers[1].value, "2");
+        assert_eq!(answers[2].part, Part::Two);
+    }
+
+    #[test]
+    fn checked_in_answers_cover_every_example() {
+        let store = AnswerStore::load(&AnswerStore::default_path()).unwrap();
+
+        for registration in aoc_day::registered_days() {
+            let day = Day::try_from(registration.day).unwrap();
+            for example in registration.examples {
+                for (part, value) in [(Part::One, example.part1), (Part::Two, example.part2)] {
+                    let Some(value) = value else {
+                        continue;
+                    };
+                    let expected = ExpectedAnswer {
+                        day,
+                        part,
+                        input: example.name.to_string(),
+                        value: value.to_string(),
+                    };
+                    assert!(
+                        store.iter().any(|answer| *answer == expected),
+                        "answers.toml does not record {expected:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn reject_bad_keys() {
+        assert!("[day26.input]\npart1 = 1".parse::<AnswerStore>().is_err());
+        assert!("[day01.input]\npart3 = 1".parse::<AnswerStore>().is_err());
+        assert!("[day01.input]\npart1 = 1.5".parse::<AnswerStore>().is_err());
+    }
+}
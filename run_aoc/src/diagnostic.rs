@@ -0,0 +1,65 @@
+use aoc_day::Diagnostic;
+
+/// Renders `diagnostic` as the offending line of input with the problem underlined
+///
+/// ```text
+/// error: expected an unsigned number, found 'x'
+///  --> line 3, column 7
+///   |
+/// 3 | 75,47,x
+///   |       ^
+/// ```
+pub fn render(diagnostic: &Diagnostic) -> String {
+    let line_number = diagnostic.line.to_string();
+    let gutter = " ".repeat(line_number.len());
+
+    // Keep tabs so the carets line up with the source line however tabs are displayed
+    let indent: String = diagnostic
+        .source_line
+        .chars()
+        .take(diagnostic.column - 1)
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+    let carets = "^".repeat(diagnostic.len.max(1));
+
+    format!(
+        "error: expected {}, found {}\n\
+        {gutter}--> line {}, column {}\n\
+        {gutter} |\n\
+        {line_number} | {}\n\
+        {gutter} | {indent}{carets}",
+        diagnostic.expected,
+        diagnostic.found,
+        diagnostic.line,
+        diagnostic.column,
+        diagnostic.source_line,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn underlines_offending_text() {
+        let input = "47|53\n\n75,47,x\n";
+        let diagnostic = Diagnostic::at(input, 13, "an unsigned number");
+
+        assert_eq!(
+            render(&diagnostic),
+            "error: expected an unsigned number, found 'x'\n \
+            --> line 3, column 7\n  \
+            |\n\
+            3 | 75,47,x\n  \
+            |       ^"
+        );
+    }
+
+    #[test]
+    fn underlines_whole_token_after_tabs() {
+        let input = "1\t22\n10\tabc";
+        let diagnostic = Diagnostic::at(input, 8, "a number");
+
+        assert!(render(&diagnostic).ends_with("2 | 10\tabc\n  |   \t^^^"));
+    }
+}
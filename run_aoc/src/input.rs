@@ -0,0 +1,144 @@
+use crate::input_manager::{self, InputManager};
+use crate::Day;
+use anyhow::{bail, Context};
+use aoc_day::Example;
+use std::ffi::OsString;
+use std::io::Read;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// Environment variable which overrides the directory holding `dayNN.txt` inputs
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Directory searched for puzzle inputs
+///
+/// Uses [INPUT_DIR_VAR] when it is set, falling back to the `inputs` directory of the workspace.
+pub fn input_dir() -> PathBuf {
+    input_dir_from(std::env::var_os(INPUT_DIR_VAR))
+}
+
+fn input_dir_from(env_value: Option<OsString>) -> PathBuf {
+    match env_value {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../inputs"),
+    }
+}
+
+/// Where a day's puzzle input is read from
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum InputSource {
+    /// The day's own puzzle input, downloaded into the input directory when it is missing
+    Puzzle(Day),
+    /// A file given with `--input`
+    File(PathBuf),
+    /// Standard input, selected with `--input -`
+    Stdin,
+    /// One of the examples declared by the day, selected with `--example`
+    Example(&'static Example),
+}
+
+impl InputSource {
+    /// Reads the full text of the input, downloading a missing puzzle input through the shared
+    /// [InputManager]
+    pub fn read(&self) -> anyhow::Result<String> {
+        self.read_with(InputManager::shared())
+    }
+
+    /// Reads the full text of the input, downloading a missing puzzle input through `manager`
+    pub fn read_with(&self, manager: Option<&InputManager>) -> anyhow::Result<String> {
+        match self {
+            Self::Puzzle(day) => {
+                let path = day.get_input_path();
+                if path.is_file() {
+                    return Self::File(path).read();
+                }
+
+                match manager {
+                    Some(manager) => {
+                        let path = manager.ensure_input(*day)?;
+                        Self::File(path).read()
+                    }
+                    None => bail!(
+                        "Input file not found: {}\n\
+                        Set {} to download it, set {INPUT_DIR_VAR} to the directory holding \
+                        dayNN.txt or pass --input <path>",
+                        path.display(),
+                        input_manager::SESSION_VAR
+                    ),
+                }
+            }
+            Self::File(path) => {
+                if !path.is_file() {
+                    bail!("Input file not found: {}", path.display());
+                }
+
+                std::fs::read_to_string(path)
+                    .with_context(|| format!("Could not read input from {}", path.display()))
+            }
+            Self::Stdin => {
+                let mut input = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut input)
+                    .context("Could not read input from stdin")?;
+                Ok(input)
+            }
+            Self::Example(example) => Ok(example.input.to_string()),
+        }
+    }
+}
+
+impl FromStr for InputSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "" => Err("Input path must not be empty".to_string()),
+            "-" => Ok(Self::Stdin),
+            path => Ok(Self::File(PathBuf::from(path))),
+        }
+    }
+}
+
+impl std::fmt::Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Puzzle(day) => write!(f, "{}", day.get_input_path().display()),
+            Self::File(path) => write!(f, "{}", path.display()),
+            Self::Stdin => write!(f, "<stdin>"),
+            Self::Example(example) => write!(f, "example '{}'", example.name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_input_source() {
+        assert_eq!("-".parse(), Ok(InputSource::Stdin));
+        assert_eq!(
+            "my/input.txt".parse(),
+            Ok(InputSource::File(PathBuf::from("my/input.txt")))
+        );
+        assert!("".parse::<InputSource>().is_err());
+    }
+
+    #[test]
+    fn input_dir_override() {
+        assert_eq!(
+            input_dir_from(Some(OsString::from("/tmp/aoc"))),
+            PathBuf::from("/tmp/aoc")
+        );
+        assert!(input_dir_from(None).ends_with("inputs"));
+        assert!(input_dir_from(Some(OsString::new())).ends_with("inputs"));
+    }
+
+    #[test]
+    fn missing_file_error() {
+        let source = InputSource::File(PathBuf::from("does/not/exist.txt"));
+        let err = source.read().unwrap_err();
+
+        assert!(err.to_string().contains("does/not/exist.txt"));
+    }
+}
@@ -0,0 +1,270 @@
+use crate::Day;
+use anyhow::{bail, Context};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Environment variable holding the session token used to download puzzle inputs
+pub const SESSION_VAR: &str = "AOC_SESSION";
+/// Environment variable which overrides the server inputs are downloaded from
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const YEAR: u16 = 2024;
+const USER_AGENT: &str = concat!(
+    "github.com/iAmSomeone2/advent-of-code-2024 run-aoc/",
+    env!("CARGO_PKG_VERSION")
+);
+/// Minimum time between two requests to the puzzle server
+const DEFAULT_MIN_INTERVAL: Duration = Duration::from_secs(5);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Session token from [SESSION_VAR], if one is set
+pub fn session_from_env() -> Option<String> {
+    std::env::var(SESSION_VAR)
+        .ok()
+        .map(|session| session.trim().to_string())
+        .filter(|session| !session.is_empty())
+}
+
+/// Puzzle server from [BASE_URL_VAR], falling back to adventofcode.com
+pub fn base_url_from_env() -> String {
+    std::env::var(BASE_URL_VAR)
+        .ok()
+        .filter(|url| !url.is_empty())
+        .unwrap_or_else(|| DEFAULT_BASE_URL.to_string())
+}
+
+/// HTTP agent configured with the runner's user agent and timeout
+pub fn build_agent() -> ureq::Agent {
+    ureq::AgentBuilder::new()
+        .timeout(REQUEST_TIMEOUT)
+        .user_agent(USER_AGENT)
+        .build()
+}
+
+/// Reads the body of a puzzle server response, turning error statuses into errors
+pub fn response_body(
+    url: &str,
+    response: Result<ureq::Response, ureq::Error>,
+) -> anyhow::Result<String> {
+    match response {
+        Ok(response) => response
+            .into_string()
+            .with_context(|| format!("Could not read response body from {url}")),
+        Err(ureq::Error::Status(code, response)) => {
+            let body = response.into_string().unwrap_or_default();
+            bail!(
+                "Request to {url} failed with status {code}: {}",
+                body.lines().next().unwrap_or_default()
+            )
+        }
+        Err(err) => Err(err).with_context(|| format!("Request to {url} failed")),
+    }
+}
+
+/// Source of puzzle inputs which are not cached yet
+pub trait InputFetcher: Send + Sync {
+    fn fetch(&self, day: Day) -> anyhow::Result<String>;
+}
+
+/// Ensures requests are spaced at least `min_interval` apart
+pub struct RateLimiter {
+    min_interval: Duration,
+    last_request: Mutex<Option<Instant>>,
+}
+
+impl RateLimiter {
+    pub fn new(min_interval: Duration) -> Self {
+        Self {
+            min_interval,
+            last_request: Mutex::new(None),
+        }
+    }
+
+    /// Blocks until another request is allowed, then records it
+    pub fn wait(&self) {
+        let mut last_request = self
+            .last_request
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        if let Some(last) = *last_request {
+            let elapsed = last.elapsed();
+            if elapsed < self.min_interval {
+                std::thread::sleep(self.min_interval - elapsed);
+            }
+        }
+
+        *last_request = Some(Instant::now());
+    }
+}
+
+/// Downloads inputs from `{base_url}/2024/day/{day}/input` using a session cookie
+pub struct HttpFetcher {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+    rate_limiter: RateLimiter,
+}
+
+impl HttpFetcher {
+    pub fn new(base_url: &str, session: &str, min_interval: Duration) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            agent: build_agent(),
+            rate_limiter: RateLimiter::new(min_interval),
+        }
+    }
+
+    /// Creates a fetcher from [SESSION_VAR] and [BASE_URL_VAR], if a session token is set
+    pub fn from_env() -> Option<Self> {
+        let session = session_from_env()?;
+        Some(Self::new(
+            &base_url_from_env(),
+            &session,
+            DEFAULT_MIN_INTERVAL,
+        ))
+    }
+}
+
+impl InputFetcher for HttpFetcher {
+    fn fetch(&self, day: Day) -> anyhow::Result<String> {
+        let url = format!("{}/{YEAR}/day/{}/input", self.base_url, u8::from(day));
+
+        self.rate_limiter.wait();
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call();
+
+        response_body(&url, response)
+    }
+}
+
+/// Provides cached puzzle inputs, downloading any that are missing
+pub struct InputManager {
+    dir: PathBuf,
+    fetcher: Box<dyn InputFetcher>,
+}
+
+impl InputManager {
+    pub fn new(dir: &Path, fetcher: Box<dyn InputFetcher>) -> Self {
+        Self {
+            dir: dir.to_path_buf(),
+            fetcher,
+        }
+    }
+
+    /// Process-wide manager for the default input directory, if a session token is configured
+    ///
+    /// Shared so that the rate limit applies across every input fetched by one invocation.
+    pub fn shared() -> Option<&'static InputManager> {
+        static MANAGER: OnceLock<Option<InputManager>> = OnceLock::new();
+
+        MANAGER
+            .get_or_init(|| {
+                let fetcher = HttpFetcher::from_env()?;
+                Some(InputManager::new(
+                    &crate::input::input_dir(),
+                    Box::new(fetcher),
+                ))
+            })
+            .as_ref()
+    }
+
+    pub fn input_path(&self, day: Day) -> PathBuf {
+        self.dir.join(format!("day{:02}.txt", u8::from(day)))
+    }
+
+    /// Returns the path of the cached input for `day`, fetching and caching it first if needed
+    pub fn ensure_input(&self, day: Day) -> anyhow::Result<PathBuf> {
+        let path = self.input_path(day);
+        if path.is_file() {
+            return Ok(path);
+        }
+
+        let input = self.fetcher.fetch(day)?;
+        std::fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Could not create {}", self.dir.display()))?;
+
+        // Write to a temporary file first so an interrupted download is never mistaken for a
+        // cached input.
+        let partial_path = path.with_extension("txt.part");
+        std::fs::write(&partial_path, input)
+            .with_context(|| format!("Could not write {}", partial_path.display()))?;
+        std::fs::rename(&partial_path, &path)
+            .with_context(|| format!("Could not move input into {}", path.display()))?;
+
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::temp_dir;
+    use crate::test_server::{stub_server, CountingFetcher};
+    use std::sync::atomic::Ordering;
+
+    #[test]
+    fn fetches_and_caches_input() {
+        let (base_url, server) = stub_server(vec![(200, "3   4\n4   3\n")]);
+        let dir = temp_dir("fetch");
+        let fetcher = HttpFetcher::new(&base_url, "secret", Duration::ZERO);
+        let manager = InputManager::new(&dir, Box::new(fetcher));
+
+        let path = manager.ensure_input(Day(1)).unwrap();
+        assert_eq!(path, dir.join("day01.txt"));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "3   4\n4   3\n");
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2024/day/1/input HTTP/1.1"));
+        assert!(requests[0].contains("session=secret"));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn error_status_is_not_cached() {
+        let (base_url, server) = stub_server(vec![(400, "Puzzle inputs differ by user.")]);
+        let dir = temp_dir("error");
+        let fetcher = HttpFetcher::new(&base_url, "expired", Duration::ZERO);
+        let manager = InputManager::new(&dir, Box::new(fetcher));
+
+        let err = manager.ensure_input(Day(2)).unwrap_err();
+        assert!(err.to_string().contains("400"));
+        assert!(!manager.input_path(Day(2)).exists());
+
+        server.join().unwrap();
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn cached_input_is_not_fetched_again() {
+        let dir = temp_dir("cache");
+        let fetcher = CountingFetcher::default();
+        let calls = fetcher.calls();
+        let manager = InputManager::new(&dir, Box::new(fetcher));
+
+        manager.ensure_input(Day(3)).unwrap();
+        manager.ensure_input(Day(3)).unwrap();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn rate_limiter_spaces_requests() {
+        let limiter = RateLimiter::new(Duration::from_millis(50));
+        let start = Instant::now();
+
+        limiter.wait();
+        limiter.wait();
+
+        assert!(start.elapsed() >= Duration::from_millis(50));
+    }
+}
@@ -1,6 +1,39 @@
-use aoc_day::AoCDay;
-use clap::Parser;
-use std::path::PathBuf;
+use answers::AnswerStore;
+use anyhow::anyhow;
+use aoc_day::{Answer, AnswerValue, AoCError};
+use clap::{Parser, Subcommand};
+use input::InputSource;
+use report::OutputFormat;
+use selection::DaySelection;
+use std::path::{Path, PathBuf};
+
+mod answers;
+mod diagnostic;
+mod input;
+mod input_manager;
+mod report;
+mod runner;
+mod scaffold;
+mod selection;
+mod submit;
+mod summary;
+#[cfg(test)]
+mod test_dir;
+#[cfg(test)]
+mod test_server;
+mod timing;
+mod verify;
+
+// Days register themselves with `aoc_day::register_day!`, but a crate which is never referenced
+// is not linked into the binary and its registration would be lost.
+use day01 as _;
+use day02 as _;
+use day03 as _;
+use day04 as _;
+use day05 as _;
+use day06 as _;
+use day07 as _;
+use day09 as _;
 
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
 struct InvalidDayError(u8);
@@ -13,46 +46,35 @@ impl std::fmt::Display for InvalidDayError {
 
 impl std::error::Error for InvalidDayError {}
 
-#[derive(Copy, Clone, Eq, PartialEq)]
-enum Day {
-    One,
-    Two,
-    Three,
-    Four,
-    Five,
-    Six,
-    Seven,
-    Eight,
-    Nine,
+/// A day of the Advent calendar, 1 through 25
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+struct Day(u8);
+
+impl Day {
+    const MAX: u8 = 25;
 }
 
 impl TryFrom<u8> for Day {
     type Error = InvalidDayError;
 
     fn try_from(day: u8) -> Result<Self, Self::Error> {
-        match day {
-            1 => Ok(Day::One),
-            2 => Ok(Day::Two),
-            3 => Ok(Day::Three),
-            5 => Ok(Day::Five),
-            6 => Ok(Day::Six),
-            7 => Ok(Day::Seven),
-            8 => Ok(Day::Eight),
-            9 => Ok(Day::Nine),
-            _ => Err(InvalidDayError(day)),
+        if (1..=Self::MAX).contains(&day) {
+            Ok(Day(day))
+        } else {
+            Err(InvalidDayError(day))
         }
     }
 }
 
 impl From<Day> for u8 {
     fn from(day: Day) -> Self {
-        (day as u8) + 1
+        day.0
     }
 }
 
 impl From<&Day> for u8 {
     fn from(day: &Day) -> Self {
-        (*day as u8) + 1
+        day.0
     }
 }
 
@@ -63,29 +85,15 @@ impl std::fmt::Display for Day {
 }
 
 impl Day {
+    /// Default location of the day's puzzle input, `dayNN.txt` within [input::input_dir]
     fn get_input_path(&self) -> PathBuf {
-        let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-            .join("../inputs")
-            .canonicalize()
-            .unwrap();
+        let root = input::input_dir();
 
         let day_num: u8 = self.into();
         let txt_name = format!("day{:02}", day_num);
 
         root.join(&txt_name).with_extension("txt")
     }
-
-    fn get_aoc_day(&self) -> Box<dyn AoCDay> {
-        match self {
-            Self::One => Box::new(day01::Day01::default()),
-            Self::Two => Box::new(day02::Day02::default()),
-            Self::Three => Box::new(day03::Day03::default()),
-            Self::Five => Box::new(day05::Day05::default()),
-            Self::Six => Box::new(day06::Day06::default()),
-            Self::Nine => Box::new(day09::Day09::default()),
-            _ => panic!("Day not implemented yet"),
-        }
-    }
 }
 
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
@@ -99,7 +107,7 @@ impl std::fmt::Display for InvalidPartError {
 
 impl std::error::Error for InvalidPartError {}
 
-#[derive(Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
 enum Part {
     One,
     Two,
@@ -117,6 +125,10 @@ impl TryFrom<u8> for Part {
     }
 }
 
+impl Part {
+    const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
 impl From<Part> for u8 {
     fn from(part: Part) -> Self {
         (part as u8) + 1
@@ -132,26 +144,129 @@ impl std::fmt::Display for Part {
 struct RunConfig {
     day: Day,
     part: Part,
+    input: InputSource,
+    /// Print parse and solve timings after the answer
+    show_timings: bool,
+    /// Number of times to load and solve, for more stable timings
+    repeat: u32,
+    format: OutputFormat,
+    /// Submit the answer to the puzzle server after printing it
+    submit: bool,
 }
 
 impl RunConfig {
-    fn run(&self) -> anyhow::Result<()> {
-        println!("{}", self);
+    /// Runs the configured part and prints its answer
+    ///
+    /// # Return
+    ///
+    /// `false` if the input was an example and the answer did not match the example's expected
+    /// answer, or if a submitted answer was not accepted; `true`, otherwise.
+    fn run(&self) -> Result<bool, AoCError> {
+        let is_text = self.format == OutputFormat::Text;
+        if is_text {
+            println!("{}", self);
+        }
 
-        let mut aoc_day = self.day.get_aoc_day();
-        let input_path = self.day.get_input_path();
-        aoc_day.load_input(&input_path)?;
+        let outcome = runner::run_part(self.day, self.part, &self.input, self.repeat);
+        if !is_text {
+            report::print_records(std::slice::from_ref(&outcome), self.format);
+        }
 
-        match self.part {
-            Part::One => {
-                aoc_day.part1();
-            }
-            Part::Two => {
-                aoc_day.part2();
+        let answer = outcome.result?;
+        if is_text {
+            println!("{}", answer);
+
+            if self.show_timings {
+                println!("Parse:\t{}", outcome.parse_time);
+                println!("Solve:\t{}", outcome.solve_time);
             }
         }
 
-        Ok(())
+        if self.submit {
+            return self.submit_answer(&answer);
+        }
+
+        let InputSource::Example(example) = &self.input else {
+            return Ok(true);
+        };
+
+        let expected = match self.part {
+            Part::One => example.part1,
+            Part::Two => example.part2,
+        };
+        let actual = answer.value.to_string();
+        let (passed, message) = match expected {
+            Some(expected) if expected == actual => (true, "pass".to_string()),
+            Some(expected) => (false, format!("FAIL (expected {expected})")),
+            None => (true, "no expected answer for this part".to_string()),
+        };
+        if is_text {
+            println!("Example:\t{message}");
+        }
+
+        Ok(passed)
+    }
+
+    /// Submits `answer` for the configured part and prints the server's verdict
+    fn submit_answer(&self, answer: &Answer) -> Result<bool, AoCError> {
+        if let AnswerValue::Lines(_) = answer.value {
+            return Err(anyhow!("Multi-line answers cannot be submitted").into());
+        }
+
+        let Some(submitter) = submit::Submitter::from_env() else {
+            return Err(anyhow!(
+                "Submitting requires a session token in {}",
+                input_manager::SESSION_VAR
+            )
+            .into());
+        };
+
+        let outcome = submitter.submit(self.day, self.part, &answer.value.to_string())?;
+        if self.format == OutputFormat::Text {
+            println!("Submit:\t{outcome}");
+        } else {
+            eprintln!("Submit: {outcome}");
+        }
+
+        Ok(outcome.verdict().is_solved())
+    }
+}
+
+/// Runs both parts of every implemented day in the selection and prints a summary table
+///
+/// # Return
+///
+/// The process exit code; failures are reported in the table rather than stopping the run.
+fn run_selection(
+    selection: DaySelection,
+    repeat: u32,
+    show_timings: bool,
+    format: OutputFormat,
+) -> i32 {
+    let outcomes: Vec<_> = selection
+        .days()
+        .into_iter()
+        .filter(|day| aoc_day::find_day(day.into()).is_some())
+        .flat_map(|day| {
+            let input = InputSource::Puzzle(day);
+            Part::ALL.map(|part| runner::run_part(day, part, &input, repeat))
+        })
+        .collect();
+
+    if outcomes.is_empty() {
+        eprintln!("No implemented days in the selection");
+        return EXIT_NOT_IMPLEMENTED;
+    }
+
+    match format {
+        OutputFormat::Text => summary::print_summary(&outcomes, show_timings),
+        _ => report::print_records(&outcomes, format),
+    }
+
+    if outcomes.iter().any(runner::PartOutcome::is_failure) {
+        EXIT_INTERNAL
+    } else {
+        0
     }
 }
 
@@ -170,43 +285,277 @@ impl std::fmt::Display for RunConfig {
     }
 }
 
+/// Exit code used when the command line arguments are invalid
+const EXIT_INVALID_ARGS: i32 = 1;
+/// Exit code used when a day fails for reasons unrelated to its input
+const EXIT_INTERNAL: i32 = 2;
+/// Exit code used when the requested part has not been solved yet
+const EXIT_NOT_IMPLEMENTED: i32 = 3;
+/// Exit code used when the puzzle input could not be parsed
+const EXIT_BAD_INPUT: i32 = 4;
+/// Exit code used when an answer differs from the recorded or example answer, or a submitted
+/// answer is not accepted
+const EXIT_VERIFY_FAILED: i32 = 5;
+
 /// Advent of Code 2024 runner
 #[derive(Parser, Debug)]
-#[command(about, long_about = None)]
+#[command(
+    about,
+    long_about = None,
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
+)]
 struct Args {
-    /// Which day to run
-    day: u8,
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// Which day to run: a single day, a range such as `1-9`, or `all`
+    ///
+    /// Ranges and `all` run both parts of every implemented day and print a summary table.
+    #[arg(required = true)]
+    day: Option<DaySelection>,
     /// Which part to run
     #[arg(short, long, default_value = "1")]
     part: u8,
+    /// Print how long parsing and solving took
+    ///
+    /// For ranges, the full timings of every part are listed below the summary table.
+    #[arg(short, long)]
+    time: bool,
+    /// Run each part this many times and report min/median/max timings
+    #[arg(short, long, default_value = "1", value_parser = clap::value_parser!(u32).range(1..))]
+    repeat: u32,
+    /// Read the puzzle input from this file instead, or from stdin when `-`
+    ///
+    /// Only valid when running a single day. By default inputs are read from `dayNN.txt` in the
+    /// directory named by `AOC_INPUT_DIR`, or the workspace `inputs` directory. Missing inputs
+    /// are downloaded there when `AOC_SESSION` holds a session token.
+    #[arg(short, long)]
+    input: Option<InputSource>,
+    /// Run against one of the day's bundled examples instead of the puzzle input
+    #[arg(
+        short,
+        long,
+        value_name = "NAME",
+        num_args = 0..=1,
+        default_missing_value = "example",
+        conflicts_with = "input"
+    )]
+    example: Option<String>,
+    /// Output format; `json` and `csv` emit one record per part run for use by other tools
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+    /// Submit the answer to the puzzle server, using the session token in `AOC_SESSION`
+    ///
+    /// Submissions are recorded in `submissions/dayNN.json` within the input directory. An answer
+    /// which has already been judged, or any answer for a part which has already been solved, is
+    /// not sent again.
+    #[arg(long, conflicts_with_all = ["input", "example"])]
+    submit: bool,
+}
+
+/// Finds the example with the given name for a single-day run
+///
+/// # Return
+///
+/// The example's input, or the exit code and message to stop with
+fn example_input(day: Day, name: &str) -> Result<InputSource, (i32, String)> {
+    let Some(registration) = aoc_day::find_day(day.into()) else {
+        return Err((
+            EXIT_NOT_IMPLEMENTED,
+            format!("Day {:02} is not implemented yet", u8::from(day)),
+        ));
+    };
+
+    match registration.example(name) {
+        Some(example) => Ok(InputSource::Example(example)),
+        None => {
+            let available: Vec<_> = registration.examples.iter().map(|ex| ex.name).collect();
+            Err((
+                EXIT_INVALID_ARGS,
+                format!(
+                    "Day {:02} has no example named '{name}'. Available: {}",
+                    u8::from(day),
+                    if available.is_empty() {
+                        "none".to_string()
+                    } else {
+                        available.join(", ")
+                    }
+                ),
+            ))
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// List every day of the calendar and whether it has been implemented
+    List,
+    /// Run every part with a recorded answer and report any that no longer match
+    Verify {
+        /// Answers file to check against
+        #[arg(short, long, default_value_os_t = AnswerStore::default_path())]
+        answers: PathBuf,
+    },
+    /// Generate a new `dayNN` crate from the template and link it into the runner
+    New {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=Day::MAX as i64))]
+        day: u8,
+        /// Puzzle title shown by `list`
+        #[arg(short, long)]
+        title: Option<String>,
+    },
+}
+
+/// Generates the crate for `day` in this workspace
+///
+/// # Return
+///
+/// The process exit code
+fn new_day(day: u8, title: Option<&str>) -> i32 {
+    let day = Day::try_from(day).expect("clap restricts the day to the calendar");
+
+    match scaffold::new_day(&scaffold::workspace_root(), day, title) {
+        Ok(crate_dir) => {
+            println!("Created {}", crate_dir.display());
+            println!("Rebuild the runner to pick up the new day");
+            0
+        }
+        Err(err) => {
+            eprintln!("{:#}", err);
+            EXIT_INTERNAL
+        }
+    }
+}
+
+/// Checks every recorded answer in the file at `path`
+///
+/// # Return
+///
+/// The process exit code
+fn verify_answers(path: &Path) -> i32 {
+    let store = match AnswerStore::load(path) {
+        Ok(store) => store,
+        Err(err) => {
+            eprintln!("{:#}", err);
+            return EXIT_INTERNAL;
+        }
+    };
+
+    if store.is_empty() {
+        eprintln!("No answers recorded in {}", path.display());
+        return 0;
+    }
+
+    if verify::verify_all(&store) {
+        0
+    } else {
+        EXIT_VERIFY_FAILED
+    }
+}
+
+/// Prints every day of the calendar along with the title of each implemented day
+fn list_days() {
+    let registered = aoc_day::registered_days();
+
+    for day in 1..=Day::MAX {
+        match registered
+            .iter()
+            .find(|registration| registration.day == day)
+        {
+            Some(registration) => println!("{:02}  {}", day, registration.title),
+            None => println!("{:02}  -", day),
+        }
+    }
 }
 
 fn main() {
     let cli = Args::parse();
 
-    let day: Day = match cli.day.try_into() {
-        Ok(day) => day,
-        Err(err) => {
-            eprintln!("{}", err);
-            std::process::exit(1);
+    match cli.command {
+        Some(Command::List) => {
+            list_days();
+            std::process::exit(0);
         }
+        Some(Command::Verify { answers }) => std::process::exit(verify_answers(&answers)),
+        Some(Command::New { day, title }) => std::process::exit(new_day(day, title.as_deref())),
+        None => {}
+    }
+
+    let day = match cli.day {
+        Some(DaySelection::Single(day)) => day,
+        Some(selection) => {
+            if cli.input.is_some() || cli.example.is_some() || cli.submit {
+                eprintln!(
+                    "--input, --example and --submit can only be used when running a single day"
+                );
+                std::process::exit(EXIT_INVALID_ARGS);
+            }
+            std::process::exit(run_selection(selection, cli.repeat, cli.time, cli.format))
+        }
+        None => unreachable!("clap requires a day when no subcommand is given"),
     };
 
     let part: Part = match cli.part.try_into() {
         Ok(part) => part,
         Err(err) => {
             eprintln!("{}", err);
-            std::process::exit(1);
+            std::process::exit(EXIT_INVALID_ARGS);
         }
     };
 
-    let run_config = RunConfig { day, part };
+    let input = match (cli.input, cli.example) {
+        (Some(input), _) => input,
+        (None, Some(name)) => match example_input(day, &name) {
+            Ok(input) => input,
+            Err((code, err)) => {
+                eprintln!("{}", err);
+                std::process::exit(code);
+            }
+        },
+        (None, None) => InputSource::Puzzle(day),
+    };
+
+    let run_config = RunConfig {
+        day,
+        part,
+        input,
+        show_timings: cli.time,
+        repeat: cli.repeat,
+        format: cli.format,
+        submit: cli.submit,
+    };
 
     match run_config.run() {
-        Ok(()) => std::process::exit(0),
+        Ok(true) => std::process::exit(0),
+        Ok(false) => std::process::exit(EXIT_VERIFY_FAILED),
         Err(err) => {
-            eprintln!("{}", err);
-            std::process::exit(2);
+            let code = match &err {
+                AoCError::NotImplemented => {
+                    if aoc_day::find_day(day.into()).is_some() {
+                        eprintln!(
+                            "Day {:02} part {} is not implemented yet",
+                            u8::from(day),
+                            u8::from(part)
+                        );
+                    } else {
+                        eprintln!("Day {:02} is not implemented yet", u8::from(day));
+                    }
+                    EXIT_NOT_IMPLEMENTED
+                }
+                AoCError::BadInput(_) => {
+                    eprintln!("{}", err);
+                    EXIT_BAD_INPUT
+                }
+                AoCError::Parse(diagnostic) => {
+                    eprintln!("{}", diagnostic::render(diagnostic));
+                    EXIT_BAD_INPUT
+                }
+                AoCError::Internal(_) => {
+                    eprintln!("{}", err);
+                    EXIT_INTERNAL
+                }
+            };
+            std::process::exit(code);
         }
     }
 }
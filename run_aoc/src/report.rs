@@ -0,0 +1,199 @@
+use crate::input::InputSource;
+use crate::runner::PartOutcome;
+use crate::Part;
+use clap::ValueEnum;
+use serde::Serialize;
+use std::time::Duration;
+
+/// How run results are written to stdout
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human readable banner or summary table
+    #[default]
+    Text,
+    /// A JSON array with one object per part run
+    Json,
+    /// A header row followed by one row per part run
+    Csv,
+}
+
+/// Flat, machine-readable form of a [PartOutcome]
+#[derive(Debug, Serialize)]
+pub struct RunRecord {
+    pub day: u8,
+    pub part: u8,
+    /// [Status::key](crate::runner::Status::key) of how the run ended: `ok`, `not_implemented`, `bad_input` or `error`
+    pub status: &'static str,
+    pub label: Option<String>,
+    pub answer: Option<String>,
+    /// Expected answer when running against an example that declares one
+    pub expected: Option<String>,
+    pub error: Option<String>,
+    pub input: String,
+    pub input_sha256: Option<String>,
+    pub runs: usize,
+    pub parse_min_ns: u128,
+    pub parse_median_ns: u128,
+    pub parse_max_ns: u128,
+    pub solve_min_ns: u128,
+    pub solve_median_ns: u128,
+    pub solve_max_ns: u128,
+}
+
+const CSV_HEADER: &str = "day,part,status,label,answer,expected,error,input,input_sha256,runs,\
+    parse_min_ns,parse_median_ns,parse_max_ns,solve_min_ns,solve_median_ns,solve_max_ns";
+
+fn nanos(duration: Duration) -> u128 {
+    duration.as_nanos()
+}
+
+impl From<&PartOutcome> for RunRecord {
+    fn from(outcome: &PartOutcome) -> Self {
+        let (label, answer, error) = match &outcome.result {
+            Ok(answer) => (
+                Some(answer.label.clone()),
+                Some(answer.value.to_string()),
+                None,
+            ),
+            Err(err) => (None, None, Some(err.to_string())),
+        };
+        let expected = match (&outcome.input, outcome.part) {
+            (InputSource::Example(example), Part::One) => example.part1,
+            (InputSource::Example(example), Part::Two) => example.part2,
+            _ => None,
+        };
+
+        Self {
+            day: outcome.day.into(),
+            part: outcome.part.into(),
+            status: outcome.status().key(),
+            label,
+            answer,
+            expected: expected.map(str::to_string),
+            error,
+            input: outcome.input.to_string(),
+            input_sha256: outcome.input_hash.clone(),
+            runs: outcome.solve_time.len(),
+            parse_min_ns: nanos(outcome.parse_time.min()),
+            parse_median_ns: nanos(outcome.parse_time.median()),
+            parse_max_ns: nanos(outcome.parse_time.max()),
+            solve_min_ns: nanos(outcome.solve_time.min()),
+            solve_median_ns: nanos(outcome.solve_time.median()),
+            solve_max_ns: nanos(outcome.solve_time.max()),
+        }
+    }
+}
+
+/// Quotes a CSV field when it contains a delimiter, quote or line break
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+impl RunRecord {
+    fn to_csv_row(&self) -> String {
+        let optional = |value: &Option<String>| csv_field(value.as_deref().unwrap_or_default());
+
+        [
+            self.day.to_string(),
+            self.part.to_string(),
+            self.status.to_string(),
+            optional(&self.label),
+            optional(&self.answer),
+            optional(&self.expected),
+            optional(&self.error),
+            csv_field(&self.input),
+            optional(&self.input_sha256),
+            self.runs.to_string(),
+            self.parse_min_ns.to_string(),
+            self.parse_median_ns.to_string(),
+            self.parse_max_ns.to_string(),
+            self.solve_min_ns.to_string(),
+            self.solve_median_ns.to_string(),
+            self.solve_max_ns.to_string(),
+        ]
+        .join(",")
+    }
+}
+
+/// Writes the outcomes to stdout in a machine-readable format
+///
+/// [OutputFormat::Text] is handled by the callers, which each have their own layout.
+pub fn print_records(outcomes: &[PartOutcome], format: OutputFormat) {
+    let records: Vec<RunRecord> = outcomes.iter().map(RunRecord::from).collect();
+
+    match format {
+        OutputFormat::Json => {
+            let json = serde_json::to_string_pretty(&records)
+                .expect("RunRecord only contains serializable fields");
+            println!("{json}");
+        }
+        OutputFormat::Csv => {
+            println!("{CSV_HEADER}");
+            for record in &records {
+                println!("{}", record.to_csv_row());
+            }
+        }
+        OutputFormat::Text => unreachable!("text output is printed by the caller"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_spellings() {
+        let day = crate::Day::try_from(1).unwrap();
+        let outcome = PartOutcome {
+            day,
+            part: Part::Two,
+            input: InputSource::Puzzle(day),
+            input_hash: None,
+            result: Err(aoc_day::AoCError::NotImplemented),
+            parse_time: Default::default(),
+            solve_time: Default::default(),
+        };
+
+        assert_eq!(RunRecord::from(&outcome).status, "not_implemented");
+        assert_eq!(outcome.status().to_string(), "not implemented");
+    }
+
+    #[test]
+    fn csv_quoting() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn csv_header_matches_row() {
+        let record = RunRecord {
+            day: 1,
+            part: 2,
+            status: "ok",
+            label: Some("Similarity score".to_string()),
+            answer: Some("31".to_string()),
+            expected: None,
+            error: None,
+            input: "inputs/day01.txt".to_string(),
+            input_sha256: None,
+            runs: 1,
+            parse_min_ns: 1,
+            parse_median_ns: 2,
+            parse_max_ns: 3,
+            solve_min_ns: 4,
+            solve_median_ns: 5,
+            solve_max_ns: 6,
+        };
+
+        let header_columns = CSV_HEADER.split(',').count();
+        assert_eq!(record.to_csv_row().split(',').count(), header_columns);
+        assert!(record
+            .to_csv_row()
+            .starts_with("1,2,ok,Similarity score,31,"));
+    }
+}
@@ -0,0 +1,175 @@
+use crate::input::InputSource;
+use crate::input_manager::InputManager;
+use crate::timing::Timings;
+use crate::{Day, Part};
+use aoc_day::{Answer, AoCError};
+use sha2::{Digest, Sha256};
+use std::time::Instant;
+
+/// How running a part ended
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Status {
+    Ok,
+    NotImplemented,
+    BadInput,
+    Error,
+}
+
+impl Status {
+    /// Machine-readable name, such as `not_implemented`
+    pub fn key(&self) -> &'static str {
+        match self {
+            Self::Ok => "ok",
+            Self::NotImplemented => "not_implemented",
+            Self::BadInput => "bad_input",
+            Self::Error => "error",
+        }
+    }
+}
+
+/// Writes the [Status::key] as words, such as `not implemented`
+impl std::fmt::Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.key().replace('_', " "))
+    }
+}
+
+/// The result of running a single part of a day, along with how long each phase took
+pub struct PartOutcome {
+    pub day: Day,
+    pub part: Part,
+    pub input: InputSource,
+    /// Hex encoded SHA-256 of the input text, if it could be read
+    pub input_hash: Option<String>,
+    pub result: Result<Answer, AoCError>,
+    /// Time spent in `load_str`, excluding reading the input
+    pub parse_time: Timings,
+    /// Time spent in `part1` or `part2`
+    pub solve_time: Timings,
+}
+
+impl PartOutcome {
+    pub fn status(&self) -> Status {
+        match &self.result {
+            Ok(_) => Status::Ok,
+            Err(AoCError::NotImplemented) => Status::NotImplemented,
+            Err(AoCError::BadInput(_) | AoCError::Parse(_)) => Status::BadInput,
+            Err(AoCError::Internal(_)) => Status::Error,
+        }
+    }
+
+    /// `true` if the part failed for a reason other than not being implemented
+    pub fn is_failure(&self) -> bool {
+        matches!(self.status(), Status::BadInput | Status::Error)
+    }
+}
+
+/// Loads the given input into a fresh instance of the day and runs the requested part
+///
+/// A day which is not registered is [AoCError::NotImplemented] without its input being read, so
+/// nothing is downloaded or read from stdin for it. The input is read once, then the load and solve are repeated `repeat` times to collect
+/// timings; the answer from the final run is kept. A new instance is created for every run since
+/// some days consume their parsed state while solving. Repetition stops at the first error.
+pub fn run_part(day: Day, part: Part, input: &InputSource, repeat: u32) -> PartOutcome {
+    run_part_with(day, part, input, repeat, InputManager::shared())
+}
+
+/// [run_part], downloading a missing puzzle input through `manager`
+fn run_part_with(
+    day: Day,
+    part: Part,
+    input: &InputSource,
+    repeat: u32,
+    manager: Option<&InputManager>,
+) -> PartOutcome {
+    let mut outcome = PartOutcome {
+        day,
+        part,
+        input: input.clone(),
+        input_hash: None,
+        result: Err(AoCError::NotImplemented),
+        parse_time: Timings::default(),
+        solve_time: Timings::default(),
+    };
+
+    let Some(registration) = aoc_day::find_day(day.into()) else {
+        return outcome;
+    };
+
+    let text = match input.read_with(manager) {
+        Ok(text) => text,
+        Err(err) => {
+            outcome.result = Err(err.into());
+            return outcome;
+        }
+    };
+    outcome.input_hash = Some(hash_input(&text));
+
+    for _ in 0..repeat.max(1) {
+        let mut aoc_day = registration.create();
+
+        let start = Instant::now();
+        let loaded = aoc_day.load_str(&text);
+        outcome.parse_time.record(start.elapsed());
+        if let Err(err) = loaded {
+            outcome.result = Err(err);
+            break;
+        }
+
+        let start = Instant::now();
+        outcome.result = match part {
+            Part::One => aoc_day.part1(),
+            Part::Two => aoc_day.part2(),
+        };
+        outcome.solve_time.record(start.elapsed());
+
+        if outcome.result.is_err() {
+            break;
+        }
+    }
+
+    outcome
+}
+
+fn hash_input(text: &str) -> String {
+    Sha256::digest(text.as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::temp_dir;
+    use crate::test_server::CountingFetcher;
+    use std::sync::atomic::Ordering;
+
+    #[test]
+    fn unregistered_day_is_not_implemented_before_reading_input() {
+        let day = Day::try_from(8).unwrap();
+        let input = InputSource::File("no/such/day08.txt".into());
+
+        let outcome = run_part(day, Part::One, &input, 1);
+
+        assert!(matches!(outcome.result, Err(AoCError::NotImplemented)));
+        assert_eq!(outcome.input_hash, None);
+    }
+
+    #[test]
+    fn unregistered_day_is_not_downloaded() {
+        let dir = temp_dir("runner_unregistered");
+        let fetcher = CountingFetcher::default();
+        let calls = fetcher.calls();
+        let manager = InputManager::new(&dir, Box::new(fetcher));
+        let day = Day::try_from(8).unwrap();
+
+        for part in Part::ALL {
+            let outcome = run_part_with(day, part, &InputSource::Puzzle(day), 1, Some(&manager));
+            assert!(matches!(outcome.result, Err(AoCError::NotImplemented)));
+        }
+
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+        assert!(!dir.exists());
+    }
+}
@@ -0,0 +1,187 @@
+use crate::Day;
+use anyhow::{bail, Context};
+use std::path::{Path, PathBuf};
+
+const CARGO_TEMPLATE: &str = include_str!("../templates/Cargo.toml.tmpl");
+const LIB_TEMPLATE: &str = include_str!("../templates/lib.rs.tmpl");
+const BENCH_TEMPLATE: &str = include_str!("../templates/bench.rs.tmpl");
+
+/// Root of the workspace this runner was built from
+pub fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("the runner is a member of the workspace")
+        .to_path_buf()
+}
+
+/// Generates a `dayNN` crate under `root` and links it into the runner
+///
+/// The new crate is picked up by the workspace's `day*` member glob; the runner needs a
+/// dependency on it and a `use dayNN as _;` line so that its registration is linked in.
+///
+/// # Return
+///
+/// The directory of the new crate
+pub fn new_day(root: &Path, day: Day, title: Option<&str>) -> anyhow::Result<PathBuf> {
+    let day_num = u8::from(day);
+    let crate_name = format!("day{day_num:02}");
+    let crate_dir = root.join(&crate_name);
+    if crate_dir.exists() {
+        bail!("{} already exists", crate_dir.display());
+    }
+
+    // Rendered as a Rust string literal, so that quotes and backslashes in the title are escaped.
+    let title = format!("{:?}", title.unwrap_or(&format!("Day {day_num}")));
+    let render = |template: &str| {
+        template
+            .replace("{{crate}}", &crate_name)
+            .replace("{{type}}", &format!("Day{day_num:02}"))
+            .replace("{{day}}", &day_num.to_string())
+            .replace("{{title}}", &title)
+    };
+
+    // Edit the runner first so that nothing is left half-generated if it has an unexpected shape.
+    let manifest_path = root.join("run_aoc").join("Cargo.toml");
+    let main_path = root.join("run_aoc").join("src").join("main.rs");
+    let manifest = insert_sorted_line(
+        &read(&manifest_path)?,
+        &format!("{crate_name} = {{ path = \"../{crate_name}\" }}"),
+        |line| is_day_line(line, ""),
+    )
+    .with_context(|| format!("Could not add {crate_name} to {}", manifest_path.display()))?;
+    let main = insert_sorted_line(
+        &read(&main_path)?,
+        &format!("use {crate_name} as _;"),
+        |line| is_day_line(line, "use "),
+    )
+    .with_context(|| format!("Could not link {crate_name} in {}", main_path.display()))?;
+
+    write(&crate_dir.join("Cargo.toml"), &render(CARGO_TEMPLATE))?;
+    write(&crate_dir.join("src").join("lib.rs"), &render(LIB_TEMPLATE))?;
+    write(
+        &crate_dir.join("benches").join(format!("{crate_name}.rs")),
+        &render(BENCH_TEMPLATE),
+    )?;
+    write(&crate_dir.join("example_input.txt"), "")?;
+    write(&manifest_path, &manifest)?;
+    write(&main_path, &main)?;
+
+    Ok(crate_dir)
+}
+
+/// Whether `line` starts with `prefix` followed by a day crate name such as `day07`
+fn is_day_line(line: &str, prefix: &str) -> bool {
+    line.strip_prefix(prefix)
+        .and_then(|rest| rest.strip_prefix("day"))
+        .is_some_and(|rest| rest.len() > 2 && rest.as_bytes()[..2].iter().all(u8::is_ascii_digit))
+}
+
+/// Inserts `new_line` among the lines matching `is_peer`, keeping them sorted
+fn insert_sorted_line(
+    text: &str,
+    new_line: &str,
+    is_peer: impl Fn(&str) -> bool,
+) -> anyhow::Result<String> {
+    let mut lines: Vec<&str> = text.lines().collect();
+
+    let peers: Vec<usize> = (0..lines.len()).filter(|&i| is_peer(lines[i])).collect();
+    let Some(&last_peer) = peers.last() else {
+        bail!("no existing day entries to add '{new_line}' next to");
+    };
+    if peers.iter().any(|&i| lines[i] == new_line) {
+        bail!("'{new_line}' is already present");
+    }
+
+    let insert_at = peers
+        .iter()
+        .copied()
+        .find(|&i| lines[i] > new_line)
+        .unwrap_or(last_peer + 1);
+    lines.insert(insert_at, new_line);
+
+    let mut result = lines.join("\n");
+    if text.ends_with('\n') {
+        result.push('\n');
+    }
+    Ok(result)
+}
+
+fn read(path: &Path) -> anyhow::Result<String> {
+    std::fs::read_to_string(path).with_context(|| format!("Could not read {}", path.display()))
+}
+
+fn write(path: &Path, contents: &str) -> anyhow::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Could not create {}", dir.display()))?;
+    }
+
+    std::fs::write(path, contents).with_context(|| format!("Could not write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::temp_dir;
+
+    const MANIFEST: &str = "[dependencies]\naoc_day = { path = \"../aoc_day\" }\n\
+        day01 = { path = \"../day01\" }\nday09 = { path = \"../day09\" }\nanyhow = \"~1\"\n";
+    const MAIN: &str =
+        "use clap::Parser;\n\n// Link day crates\nuse day01 as _;\nuse day09 as _;\n\
+        \nfn main() {}\n";
+
+    fn temp_workspace(name: &str) -> PathBuf {
+        let root = temp_dir(name);
+        write(&root.join("run_aoc").join("Cargo.toml"), MANIFEST).unwrap();
+        write(&root.join("run_aoc").join("src").join("main.rs"), MAIN).unwrap();
+        root
+    }
+
+    #[test]
+    fn insert_keeps_day_lines_sorted() {
+        let peer = |line: &str| is_day_line(line, "use ");
+
+        let text = insert_sorted_line(MAIN, "use day04 as _;", peer).unwrap();
+        assert!(text.contains("use day01 as _;\nuse day04 as _;\nuse day09 as _;\n"));
+
+        let text = insert_sorted_line(&text, "use day12 as _;", peer).unwrap();
+        assert!(text.contains("use day09 as _;\nuse day12 as _;\n\nfn main"));
+
+        assert!(insert_sorted_line(&text, "use day12 as _;", peer).is_err());
+        assert!(insert_sorted_line("fn main() {}", "use day12 as _;", peer).is_err());
+    }
+
+    #[test]
+    fn new_day_generates_crate() {
+        let root = temp_workspace("scaffold");
+        let day = Day::try_from(4).unwrap();
+
+        let crate_dir = new_day(&root, day, Some("Ceres Search")).unwrap();
+        assert_eq!(crate_dir, root.join("day04"));
+
+        let lib = read(&crate_dir.join("src").join("lib.rs")).unwrap();
+        assert!(lib.contains("pub struct Day04 {}"));
+        assert!(lib.contains("register_day!(4, \"Ceres Search\", Day04"));
+        assert!(!lib.contains("{{"));
+        assert!(read(&crate_dir.join("Cargo.toml"))
+            .unwrap()
+            .contains("name = \"day04\""));
+        assert!(crate_dir.join("benches").join("day04.rs").is_file());
+        assert!(crate_dir.join("example_input.txt").is_file());
+
+        let manifest = read(&root.join("run_aoc").join("Cargo.toml")).unwrap();
+        assert!(manifest
+            .contains("day01 = { path = \"../day01\" }\nday04 = { path = \"../day04\" }\nday09"));
+        let main = read(&root.join("run_aoc").join("src").join("main.rs")).unwrap();
+        assert!(main.contains("use day04 as _;"));
+
+        let day = Day::try_from(8).unwrap();
+        let crate_dir = new_day(&root, day, Some(r#"Resonant "Collinearity" \o/"#)).unwrap();
+        let lib = read(&crate_dir.join("src").join("lib.rs")).unwrap();
+        assert!(lib.contains(r#"register_day!(8, "Resonant \"Collinearity\" \\o/", Day08"#));
+
+        assert!(new_day(&root, day, None).is_err());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,88 @@
+use crate::{Day, InvalidDayError};
+use std::str::FromStr;
+
+/// The day(s) requested on the command line
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum DaySelection {
+    /// A single day, e.g. `6`
+    Single(Day),
+    /// An inclusive range of days, e.g. `1-9`
+    Range(Day, Day),
+    /// Every day of the calendar
+    All,
+}
+
+impl DaySelection {
+    /// All days covered by this selection, in order
+    pub fn days(&self) -> Vec<Day> {
+        let (first, last) = match self {
+            Self::Single(day) => (*day, *day),
+            Self::Range(first, last) => (*first, *last),
+            Self::All => (Day(1), Day(Day::MAX)),
+        };
+
+        (u8::from(first)..=u8::from(last)).map(Day).collect()
+    }
+}
+
+fn parse_day(s: &str) -> Result<Day, String> {
+    let day: u8 = s
+        .trim()
+        .parse()
+        .map_err(|_| format!("Invalid day: {}", s.trim()))?;
+
+    Day::try_from(day).map_err(|err: InvalidDayError| err.to_string())
+}
+
+impl FromStr for DaySelection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("all") {
+            return Ok(Self::All);
+        }
+
+        match s.split_once('-') {
+            Some((first, last)) => {
+                let first = parse_day(first)?;
+                let last = parse_day(last)?;
+                if first > last {
+                    return Err(format!("Invalid day range: {s}"));
+                }
+
+                Ok(Self::Range(first, last))
+            }
+            None => Ok(Self::Single(parse_day(s)?)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_single() {
+        assert_eq!("6".parse(), Ok(DaySelection::Single(Day(6))));
+        assert!("0".parse::<DaySelection>().is_err());
+        assert!("26".parse::<DaySelection>().is_err());
+    }
+
+    #[test]
+    fn parse_range() {
+        let selection: DaySelection = "1-9".parse().unwrap();
+
+        assert_eq!(selection, DaySelection::Range(Day(1), Day(9)));
+        assert_eq!(selection.days().len(), 9);
+        assert!("9-1".parse::<DaySelection>().is_err());
+        assert!("1-x".parse::<DaySelection>().is_err());
+    }
+
+    #[test]
+    fn parse_all() {
+        let selection: DaySelection = "all".parse().unwrap();
+
+        assert_eq!(selection, DaySelection::All);
+        assert_eq!(selection.days().len(), 25);
+    }
+}
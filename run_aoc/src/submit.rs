@@ -0,0 +1,493 @@
+use crate::input_manager::{self, YEAR};
+use crate::{Day, Part};
+use anyhow::{bail, Context};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Lockout assumed when the server asks us to wait without saying for how long
+const DEFAULT_WAIT: Duration = Duration::from_secs(60);
+
+/// The puzzle server's judgement of a submitted answer
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint as to which direction
+    Incorrect,
+    /// Submitted too soon after a previous answer; nothing was checked
+    Wait(Duration),
+    /// The part had already been solved, so the answer was not checked
+    AlreadySolved,
+}
+
+impl Verdict {
+    /// Whether the part is known to be solved after this verdict
+    pub fn is_solved(&self) -> bool {
+        matches!(self, Self::Correct | Self::AlreadySolved)
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Correct => write!(f, "correct"),
+            Self::TooHigh => write!(f, "wrong (too high)"),
+            Self::TooLow => write!(f, "wrong (too low)"),
+            Self::Incorrect => write!(f, "wrong"),
+            Self::Wait(duration) => write!(f, "too soon, wait {}s", duration.as_secs()),
+            Self::AlreadySolved => write!(f, "already solved"),
+        }
+    }
+}
+
+/// Interprets the HTML page returned after posting an answer
+pub fn parse_response(html: &str) -> anyhow::Result<Verdict> {
+    // Only the <article> holds the message; the rest of the page is navigation.
+    let message = html
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(html, |(article, _)| article);
+
+    if message.contains("That's the right answer") {
+        Ok(Verdict::Correct)
+    } else if message.contains("That's not the right answer") {
+        if message.contains("too high") {
+            Ok(Verdict::TooHigh)
+        } else if message.contains("too low") {
+            Ok(Verdict::TooLow)
+        } else {
+            Ok(Verdict::Incorrect)
+        }
+    } else if message.contains("You gave an answer too recently") {
+        Ok(Verdict::Wait(parse_wait(message).unwrap_or(DEFAULT_WAIT)))
+    } else if message.contains("Did you already complete it") {
+        Ok(Verdict::AlreadySolved)
+    } else {
+        let snippet: String = message.chars().take(200).collect();
+        bail!(
+            "Unrecognised response from the puzzle server: {}",
+            snippet.trim()
+        )
+    }
+}
+
+/// Seconds since the Unix epoch
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs())
+}
+
+/// Parses the lockout out of "You have 1m 5s left to wait."
+fn parse_wait(message: &str) -> Option<Duration> {
+    let (_, rest) = message.split_once("You have ")?;
+    let (remaining, _) = rest.split_once(" left to wait")?;
+
+    remaining
+        .split_whitespace()
+        .try_fold(Duration::ZERO, |total, token| {
+            let unit_idx = token.find(|c: char| !c.is_ascii_digit())?;
+            let (value, unit) = token.split_at(unit_idx);
+            let value: u64 = value.parse().ok()?;
+            let secs = match unit {
+                "h" => value * 3600,
+                "m" => value * 60,
+                "s" => value,
+                _ => return None,
+            };
+            Some(total + Duration::from_secs(secs))
+        })
+}
+
+/// One answer previously sent to the puzzle server
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Submission {
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the Unix epoch
+    pub submitted_at: u64,
+}
+
+/// Every answer submitted for a single day, stored as JSON
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SubmissionHistory {
+    submissions: Vec<Submission>,
+}
+
+impl SubmissionHistory {
+    /// Loads the history at `path`, treating a missing file as an empty history
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Could not read submission history {}", path.display()))?;
+        serde_json::from_str(&text)
+            .with_context(|| format!("Could not parse submission history {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Could not create {}", dir.display()))?;
+        }
+
+        let text = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, text)
+            .with_context(|| format!("Could not write submission history {}", path.display()))
+    }
+
+    /// A previous submission of `answer` for `part` which the server actually judged
+    pub fn judged(&self, part: Part, answer: &str) -> Option<&Submission> {
+        self.submissions.iter().rev().find(|submission| {
+            submission.part == u8::from(part)
+                && submission.answer == answer
+                && !matches!(submission.verdict, Verdict::Wait(_))
+        })
+    }
+
+    /// The submission which solved `part`, if there is one
+    pub fn solved(&self, part: Part) -> Option<&Submission> {
+        self.submissions
+            .iter()
+            .find(|submission| submission.part == u8::from(part) && submission.verdict.is_solved())
+    }
+
+    /// How much longer the server asked us to wait before answering again, if at all
+    ///
+    /// The lockout applies to the whole day, whichever part it was reported for.
+    pub fn remaining_wait(&self, now: u64) -> Option<Duration> {
+        self.submissions
+            .iter()
+            .filter_map(|submission| match submission.verdict {
+                Verdict::Wait(wait) => Some(submission.submitted_at + wait.as_secs()),
+                _ => None,
+            })
+            .max()
+            .filter(|&until| until > now)
+            .map(|until| Duration::from_secs(until - now))
+    }
+
+    pub fn record(&mut self, part: Part, answer: &str, verdict: Verdict) {
+        self.submissions.push(Submission {
+            part: part.into(),
+            answer: answer.to_string(),
+            verdict,
+            submitted_at: now_secs(),
+        });
+    }
+}
+
+/// Sends answers to the puzzle server, returning the server's HTML response
+pub trait AnswerSender {
+    fn send(&self, day: Day, part: Part, answer: &str) -> anyhow::Result<String>;
+}
+
+/// Posts answers to `{base_url}/2024/day/{day}/answer` using a session cookie
+pub struct HttpSender {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl HttpSender {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            agent: input_manager::build_agent(),
+        }
+    }
+
+    /// Creates a sender from the same environment variables used to download inputs
+    pub fn from_env() -> Option<Self> {
+        let session = input_manager::session_from_env()?;
+        Some(Self::new(&input_manager::base_url_from_env(), &session))
+    }
+}
+
+impl AnswerSender for HttpSender {
+    fn send(&self, day: Day, part: Part, answer: &str) -> anyhow::Result<String> {
+        let url = format!("{}/{YEAR}/day/{}/answer", self.base_url, u8::from(day));
+        let level = u8::from(part).to_string();
+
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &level), ("answer", answer)]);
+
+        input_manager::response_body(&url, response)
+    }
+}
+
+/// What happened when an answer was handed to [Submitter::submit]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum SubmitOutcome {
+    /// The answer was sent and judged
+    Sent(Verdict),
+    /// The same answer was judged before, so it was not sent again
+    Duplicate(Submission),
+    /// The part was solved by an earlier submission, so nothing was sent
+    Solved(Submission),
+    /// The server asked us to wait before answering again and this much of the wait remains, so
+    /// nothing was sent
+    Waiting(Duration),
+}
+
+impl SubmitOutcome {
+    pub fn verdict(&self) -> Verdict {
+        match self {
+            Self::Sent(verdict) => *verdict,
+            Self::Duplicate(previous) | Self::Solved(previous) => previous.verdict,
+            Self::Waiting(remaining) => Verdict::Wait(*remaining),
+        }
+    }
+}
+
+impl Display for SubmitOutcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Sent(verdict) => write!(f, "{verdict}"),
+            Self::Duplicate(previous) => {
+                write!(
+                    f,
+                    "{} (already submitted, not sent again)",
+                    previous.verdict
+                )
+            }
+            Self::Solved(previous) => {
+                write!(f, "already solved with {}, not sent", previous.answer)
+            }
+            Self::Waiting(remaining) => {
+                write!(f, "too soon, wait {}s, not sent", remaining.as_secs())
+            }
+        }
+    }
+}
+
+/// Submits answers while keeping a per-day history in `dir`
+pub struct Submitter {
+    dir: PathBuf,
+    sender: Box<dyn AnswerSender>,
+}
+
+impl Submitter {
+    pub fn new(dir: &Path, sender: Box<dyn AnswerSender>) -> Self {
+        Self {
+            dir: dir.to_path_buf(),
+            sender,
+        }
+    }
+
+    /// Submitter for the default history directory, `submissions` within [crate::input::input_dir]
+    pub fn from_env() -> Option<Self> {
+        let sender = HttpSender::from_env()?;
+        Some(Self::new(
+            &crate::input::input_dir().join("submissions"),
+            Box::new(sender),
+        ))
+    }
+
+    pub fn history_path(&self, day: Day) -> PathBuf {
+        self.dir.join(format!("day{:02}.json", u8::from(day)))
+    }
+
+    /// Submits `answer` unless the part is solved, the same answer has already been judged or
+    /// the server's last request to wait has not yet passed
+    pub fn submit(&self, day: Day, part: Part, answer: &str) -> anyhow::Result<SubmitOutcome> {
+        let path = self.history_path(day);
+        let mut history = SubmissionHistory::load(&path)?;
+
+        if let Some(solved) = history.solved(part) {
+            return Ok(SubmitOutcome::Solved(solved.clone()));
+        }
+        if let Some(previous) = history.judged(part, answer) {
+            return Ok(SubmitOutcome::Duplicate(previous.clone()));
+        }
+        if let Some(remaining) = history.remaining_wait(now_secs()) {
+            return Ok(SubmitOutcome::Waiting(remaining));
+        }
+
+        let response = self.sender.send(day, part, answer)?;
+        let verdict = parse_response(&response)?;
+
+        history.record(part, answer, verdict);
+        history.save(&path)?;
+
+        Ok(SubmitOutcome::Sent(verdict))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::temp_dir;
+    use crate::test_server::stub_server;
+
+    const CORRECT: &str = "<main><article><p>That's the right answer! You are one gold star \
+        closer to finding the Chief Historian.</p></article></main>";
+    const TOO_HIGH: &str = "<main><article><p>That's not the right answer; your answer is too \
+        high. Please wait one minute before trying again.</p></article></main>";
+    const TOO_LOW: &str = "<article><p>That's not the right answer; your answer is too low. \
+        </p></article>";
+
+    #[test]
+    fn parse_verdicts() {
+        assert_eq!(parse_response(CORRECT).unwrap(), Verdict::Correct);
+        assert_eq!(parse_response(TOO_HIGH).unwrap(), Verdict::TooHigh);
+        assert_eq!(parse_response(TOO_LOW).unwrap(), Verdict::TooLow);
+        assert_eq!(
+            parse_response("<article><p>That's not the right answer.</p></article>").unwrap(),
+            Verdict::Incorrect
+        );
+        assert_eq!(
+            parse_response(
+                "<article><p>You don't seem to be solving the right level.  Did you already \
+                complete it?</p></article>"
+            )
+            .unwrap(),
+            Verdict::AlreadySolved
+        );
+        assert!(parse_response("<html>Please log in</html>").is_err());
+    }
+
+    #[test]
+    fn parse_wait_durations() {
+        let wait = |left: &str| {
+            parse_response(&format!(
+                "<article><p>You gave an answer too recently; you have to wait after \
+                submitting an answer before trying again.  You have {left} left to wait.\
+                </p></article>"
+            ))
+            .unwrap()
+        };
+
+        assert_eq!(wait("41s"), Verdict::Wait(Duration::from_secs(41)));
+        assert_eq!(wait("4m 2s"), Verdict::Wait(Duration::from_secs(242)));
+        assert_eq!(wait("a while"), Verdict::Wait(DEFAULT_WAIT));
+    }
+
+    #[test]
+    fn submit_posts_answer_and_records_it() {
+        let dir = temp_dir("submit_posts");
+        let (base_url, server) = stub_server(vec![(200, TOO_HIGH), (200, CORRECT)]);
+        let submitter = Submitter::new(&dir, Box::new(HttpSender::new(&base_url, "secret")));
+        let day = Day::try_from(1).unwrap();
+
+        let outcome = submitter.submit(day, Part::Two, "1234").unwrap();
+        assert_eq!(outcome, SubmitOutcome::Sent(Verdict::TooHigh));
+
+        let outcome = submitter.submit(day, Part::Two, "1000").unwrap();
+        assert_eq!(outcome, SubmitOutcome::Sent(Verdict::Correct));
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2024/day/1/answer HTTP/1.1"));
+        assert!(requests[0].contains("session=secret"));
+        assert!(requests[0].ends_with("level=2&answer=1234"));
+
+        let history = SubmissionHistory::load(&submitter.history_path(day)).unwrap();
+        assert_eq!(history.submissions.len(), 2);
+        assert_eq!(history.solved(Part::Two).unwrap().answer, "1000");
+        assert!(history.solved(Part::One).is_none());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn submit_never_resends_judged_answers() {
+        let dir = temp_dir("submit_resend");
+        let (base_url, server) = stub_server(vec![(200, TOO_LOW)]);
+        let submitter = Submitter::new(&dir, Box::new(HttpSender::new(&base_url, "secret")));
+        let day = Day::try_from(3).unwrap();
+
+        submitter.submit(day, Part::One, "10").unwrap();
+        let outcome = submitter.submit(day, Part::One, "10").unwrap();
+
+        assert!(matches!(outcome, SubmitOutcome::Duplicate(_)));
+        assert_eq!(outcome.verdict(), Verdict::TooLow);
+        // The stub only serves one response, so a second request would never be answered.
+        assert_eq!(server.join().unwrap().len(), 1);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn submit_refuses_during_wait() {
+        let dir = temp_dir("submit_during_wait");
+        let path = dir.join("day05.json");
+        let mut history = SubmissionHistory::default();
+        history.record(Part::One, "143", Verdict::Wait(Duration::from_secs(300)));
+        history.save(&path).unwrap();
+
+        // No server is running, so sending anything would fail.
+        let submitter = Submitter::new(&dir, Box::new(HttpSender::new("http://127.0.0.1:9", "")));
+        let day = Day::try_from(5).unwrap();
+
+        for part in Part::ALL {
+            let outcome = submitter.submit(day, part, "143").unwrap();
+            let SubmitOutcome::Waiting(remaining) = outcome else {
+                panic!("submitted during a wait: {outcome:?}");
+            };
+            assert!(remaining > Duration::from_secs(290));
+            assert!(!outcome.verdict().is_solved());
+        }
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn remaining_wait() {
+        let mut history = SubmissionHistory::default();
+        assert_eq!(history.remaining_wait(1000), None);
+
+        for (verdict, submitted_at) in [
+            (Verdict::Wait(Duration::from_secs(60)), 900),
+            (Verdict::TooHigh, 950),
+            (Verdict::Wait(Duration::from_secs(30)), 940),
+        ] {
+            history.submissions.push(Submission {
+                part: 1,
+                answer: "1".to_string(),
+                verdict,
+                submitted_at,
+            });
+        }
+
+        assert_eq!(history.remaining_wait(930), Some(Duration::from_secs(40)));
+        assert_eq!(history.remaining_wait(970), None);
+    }
+
+    #[test]
+    fn submit_retries_after_wait() {
+        let dir = temp_dir("submit_wait");
+        let path = dir.join("day05.json");
+        let mut history = SubmissionHistory::default();
+        history.submissions.push(Submission {
+            part: 1,
+            answer: "143".to_string(),
+            verdict: Verdict::Wait(Duration::from_secs(30)),
+            submitted_at: now_secs() - 31,
+        });
+        history.save(&path).unwrap();
+
+        let (base_url, server) = stub_server(vec![(200, CORRECT)]);
+        let submitter = Submitter::new(&dir, Box::new(HttpSender::new(&base_url, "secret")));
+        let day = Day::try_from(5).unwrap();
+
+        let outcome = submitter.submit(day, Part::One, "143").unwrap();
+        assert_eq!(outcome, SubmitOutcome::Sent(Verdict::Correct));
+        server.join().unwrap();
+
+        let outcome = submitter.submit(day, Part::One, "999").unwrap();
+        assert!(matches!(outcome, SubmitOutcome::Solved(_)));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
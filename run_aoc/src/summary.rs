@@ -0,0 +1,147 @@
+use crate::runner::PartOutcome;
+use aoc_day::AnswerValue;
+
+/// Width of the answer column; longer answers are truncated
+const ANSWER_WIDTH: usize = 20;
+
+fn answer_cell(outcome: &PartOutcome) -> String {
+    let answer = match &outcome.result {
+        Ok(answer) => answer,
+        Err(_) => return "-".to_string(),
+    };
+
+    let text = match &answer.value {
+        AnswerValue::Lines(lines) => format!("[{} lines]", lines.len()),
+        value => value.to_string(),
+    };
+
+    if text.chars().count() > ANSWER_WIDTH {
+        let truncated: String = text.chars().take(ANSWER_WIDTH - 1).collect();
+        format!("{truncated}…")
+    } else {
+        text
+    }
+}
+
+/// Prints one row per outcome with the answer, median phase timings and status
+///
+/// The reason for every failed part is listed below the table. With `show_timings`, the full
+/// min/median/max timings of every part follow as well.
+pub fn print_summary(outcomes: &[PartOutcome], show_timings: bool) {
+    print!("{}", summary(outcomes, show_timings));
+}
+
+fn summary(outcomes: &[PartOutcome], show_timings: bool) -> String {
+    let mut text = format!(
+        "{:<4} {:<4} {:<width$} {:>12} {:>12}  Status\n",
+        "Day",
+        "Part",
+        "Answer",
+        "Parse",
+        "Solve",
+        width = ANSWER_WIDTH
+    );
+    text.push_str(&"-".repeat(4 + 1 + 4 + 1 + ANSWER_WIDTH + 1 + 12 + 1 + 12 + 2 + 15));
+    text.push('\n');
+
+    for outcome in outcomes {
+        text.push_str(&format!(
+            "{:<4} {:<4} {:<width$} {:>12} {:>12}  {}\n",
+            format!("{:02}", u8::from(outcome.day)),
+            u8::from(outcome.part),
+            answer_cell(outcome),
+            format!("{:.2?}", outcome.parse_time.median()),
+            format!("{:.2?}", outcome.solve_time.median()),
+            outcome.status(),
+            width = ANSWER_WIDTH
+        ));
+    }
+
+    let failures: Vec<_> = outcomes
+        .iter()
+        .filter(|outcome| outcome.is_failure())
+        .collect();
+    if !failures.is_empty() {
+        text.push_str("\nFailures:\n");
+        for outcome in failures {
+            if let Err(err) = &outcome.result {
+                let message = err.to_string().replace('\n', "\n    ");
+                text.push_str(&format!("{}: {message}\n", part_name(outcome)));
+            }
+        }
+    }
+
+    if show_timings {
+        text.push_str("\nTimings:\n");
+        for outcome in outcomes {
+            text.push_str(&format!(
+                "{}: parse {} / solve {}\n",
+                part_name(outcome),
+                outcome.parse_time,
+                outcome.solve_time
+            ));
+        }
+    }
+
+    text
+}
+
+fn part_name(outcome: &PartOutcome) -> String {
+    format!(
+        "Day {:02} part {}",
+        u8::from(outcome.day),
+        u8::from(outcome.part)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::InputSource;
+    use crate::timing::Timings;
+    use crate::{Day, Part};
+    use aoc_day::{Answer, AoCError};
+    use std::time::Duration;
+
+    fn outcome(day: u8, part: Part, result: Result<Answer, AoCError>) -> PartOutcome {
+        let day = Day::try_from(day).unwrap();
+        let mut timings = Timings::default();
+        timings.record(Duration::from_micros(10));
+        timings.record(Duration::from_micros(30));
+
+        PartOutcome {
+            day,
+            part,
+            input: InputSource::Puzzle(day),
+            input_hash: None,
+            result,
+            parse_time: timings.clone(),
+            solve_time: timings,
+        }
+    }
+
+    #[test]
+    fn failures_are_explained() {
+        let outcomes = [
+            outcome(1, Part::One, Ok(Answer::new("Total distance", 11))),
+            outcome(1, Part::Two, Err(AoCError::NotImplemented)),
+            outcome(2, Part::One, Err(AoCError::bad_input("no reports"))),
+        ];
+
+        let text = summary(&outcomes, false);
+        assert!(text.contains("\nFailures:\nDay 02 part 1: Bad input: no reports\n"));
+        assert!(!text.contains("Day 01 part 2:"));
+        assert!(!text.contains("Timings:"));
+    }
+
+    #[test]
+    fn timings_show_every_run() {
+        let outcomes = [outcome(1, Part::One, Ok(Answer::new("Total distance", 11)))];
+
+        let text = summary(&outcomes, true);
+        assert!(!text.contains("Failures:"));
+        assert!(text.contains(
+            "\nTimings:\nDay 01 part 1: parse min 10.00µs / median 20.00µs / max 30.00µs (2 runs)"
+        ));
+    }
+}
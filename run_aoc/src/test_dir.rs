@@ -0,0 +1,10 @@
+use std::path::PathBuf;
+
+/// An empty directory under the system temp dir, unique to this test process
+///
+/// Anything left over from an earlier run with the same `name` is removed first.
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("run_aoc_{}_{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
@@ -0,0 +1,96 @@
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+/// Durations collected for one phase across repeated runs
+#[derive(Debug, Clone, Default)]
+pub struct Timings {
+    samples: Vec<Duration>,
+}
+
+impl Timings {
+    pub fn record(&mut self, sample: Duration) {
+        self.samples.push(sample);
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn min(&self) -> Duration {
+        self.samples.iter().min().copied().unwrap_or_default()
+    }
+
+    pub fn max(&self) -> Duration {
+        self.samples.iter().max().copied().unwrap_or_default()
+    }
+
+    /// Middle sample; for an even number of samples, the mean of the two middle samples
+    pub fn median(&self) -> Duration {
+        if self.samples.is_empty() {
+            return Duration::ZERO;
+        }
+
+        let mut sorted = self.samples.clone();
+        sorted.sort_unstable();
+
+        let mid = sorted.len() / 2;
+        if sorted.len().is_multiple_of(2) {
+            (sorted[mid - 1] + sorted[mid]) / 2
+        } else {
+            sorted[mid]
+        }
+    }
+}
+
+impl Display for Timings {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.len() <= 1 {
+            write!(f, "{:.2?}", self.median())
+        } else {
+            write!(
+                f,
+                "min {:.2?} / median {:.2?} / max {:.2?} ({} runs)",
+                self.min(),
+                self.median(),
+                self.max(),
+                self.len()
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timings(micros: &[u64]) -> Timings {
+        let mut timings = Timings::default();
+        for sample in micros {
+            timings.record(Duration::from_micros(*sample));
+        }
+        timings
+    }
+
+    #[test]
+    fn min_median_max() {
+        let timings = timings(&[30, 10, 20]);
+
+        assert_eq!(timings.min(), Duration::from_micros(10));
+        assert_eq!(timings.median(), Duration::from_micros(20));
+        assert_eq!(timings.max(), Duration::from_micros(30));
+    }
+
+    #[test]
+    fn even_median() {
+        let timings = timings(&[40, 10, 20, 30]);
+
+        assert_eq!(timings.median(), Duration::from_micros(25));
+    }
+
+    #[test]
+    fn single_sample_display() {
+        let timings = timings(&[15]);
+
+        assert_eq!(timings.to_string(), "15.00µs");
+    }
+}
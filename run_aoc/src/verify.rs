@@ -0,0 +1,94 @@
+use crate::answers::{AnswerStore, ExpectedAnswer, PUZZLE_INPUT};
+use crate::input::InputSource;
+use crate::runner;
+
+/// How a recorded answer compared to the freshly computed one
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Verdict {
+    Pass,
+    /// The part ran but produced a different answer
+    Mismatch,
+    /// The part could not be run to completion
+    Fail,
+    /// The input named in the answers file is not known to the runner
+    Skipped,
+}
+
+impl std::fmt::Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let text = match self {
+            Self::Pass => "pass",
+            Self::Mismatch => "MISMATCH",
+            Self::Fail => "FAIL",
+            Self::Skipped => "skipped",
+        };
+        write!(f, "{text}")
+    }
+}
+
+/// Resolves an input name from the answers file to the puzzle input or one of the day's examples
+fn resolve_input(expected: &ExpectedAnswer) -> Option<InputSource> {
+    if expected.input == PUZZLE_INPUT {
+        return Some(InputSource::Puzzle(expected.day));
+    }
+
+    aoc_day::find_day(expected.day.into())
+        .and_then(|registration| registration.example(&expected.input))
+        .map(InputSource::Example)
+}
+
+fn verify_answer(expected: &ExpectedAnswer) -> (Verdict, String) {
+    let Some(input) = resolve_input(expected) else {
+        return (Verdict::Skipped, "-".to_string());
+    };
+
+    let outcome = runner::run_part(expected.day, expected.part, &input, 1);
+    match outcome.result {
+        Ok(answer) => {
+            let actual = answer.value.to_string();
+            let verdict = if actual == expected.value {
+                Verdict::Pass
+            } else {
+                Verdict::Mismatch
+            };
+            (verdict, actual)
+        }
+        Err(err) => (Verdict::Fail, err.to_string()),
+    }
+}
+
+/// Runs every part with a recorded answer and prints how each one compares
+///
+/// # Return
+///
+/// `true` if no part failed or produced a mismatched answer.
+pub fn verify_all(store: &AnswerStore) -> bool {
+    println!(
+        "{:<4} {:<4} {:<10} {:<20} {:<20} Result",
+        "Day", "Part", "Input", "Expected", "Actual"
+    );
+    println!(
+        "{}",
+        "-".repeat(4 + 1 + 4 + 1 + 10 + 1 + 20 + 1 + 20 + 1 + 8)
+    );
+
+    let mut all_passed = true;
+    for expected in store.iter() {
+        let (verdict, actual) = verify_answer(expected);
+        if matches!(verdict, Verdict::Mismatch | Verdict::Fail) {
+            all_passed = false;
+        }
+
+        println!(
+            "{:<4} {:<4} {:<10} {:<20} {:<20} {}",
+            format!("{:02}", u8::from(expected.day)),
+            u8::from(expected.part),
+            expected.input,
+            expected.value,
+            actual,
+            verdict
+        );
+    }
+
+    all_passed
+}